@@ -1,4 +1,7 @@
-use crate::filter::{FilterConfig, FilterPreset, FilterManager, MonitorInfo};
+use crate::filter::preset_store;
+use crate::filter::{DdcCapabilities, FilterConfig, FilterError, FilterPreset, FilterManager, GammaRampController, MonitorInfo, MonitorProfile, MonitorProfileTarget, Schedule};
+use base64::Engine;
+use std::collections::HashMap;
 use std::sync::Mutex;
 use tauri::State;
 
@@ -9,11 +12,11 @@ pub struct FilterManagerState(pub Mutex<Option<FilterManager>>);
 #[tauri::command]
 pub fn get_all_filter_presets(
     state: State<FilterManagerState>,
-) -> Result<Vec<FilterPreset>, String> {
+) -> Result<Vec<FilterPreset>, FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     Ok(manager.get_all_presets())
 }
@@ -24,16 +27,36 @@ pub fn get_monitors() -> Result<Vec<MonitorInfo>, String> {
     crate::filter::enumerate_monitors()
 }
 
+/// 在软件层面预览某份滤镜配置应用到指定显示器后的效果：截取当前画面、套用滤镜的
+/// 颜色变换、按 `scale` 缩小分辨率，编码为 PNG 并以 data URL 形式返回，供前端直接
+/// 塞进 `<img src>` 展示，不会写入任何硬件 Gamma Ramp
+#[tauri::command]
+pub fn preview_filter_config(
+    config: FilterConfig,
+    monitor_id: String,
+    scale: f32,
+) -> Result<String, String> {
+    let image = GammaRampController::render_preview(&config, &monitor_id, scale)?;
+
+    let mut png_bytes = Vec::new();
+    image
+        .write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png)
+        .map_err(|e| format!("预览图编码失败: {}", e))?;
+
+    let encoded = base64::engine::general_purpose::STANDARD.encode(&png_bytes);
+    Ok(format!("data:image/png;base64,{}", encoded))
+}
+
 /// 获取单个预设
 #[tauri::command]
 pub fn get_filter_preset(
     preset_id: String,
     state: State<FilterManagerState>,
-) -> Result<FilterPreset, String> {
+) -> Result<FilterPreset, FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.get_preset(&preset_id)
 }
@@ -45,11 +68,11 @@ pub fn create_filter_preset(
     config: FilterConfig,
     hotkey: Option<String>,
     state: State<FilterManagerState>,
-) -> Result<String, String> {
+) -> Result<String, FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.create_preset(name, config, hotkey)
 }
@@ -62,25 +85,57 @@ pub fn update_filter_preset(
     config: Option<FilterConfig>,
     hotkey: Option<Option<String>>,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.update_preset(&preset_id, name, config, hotkey)
 }
 
+/// 将默认预设克隆为一份可自由编辑的用户预设
+#[tauri::command]
+pub fn detach_filter_preset(
+    preset_id: String,
+    state: State<FilterManagerState>,
+) -> Result<String, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.detach_preset(&preset_id)
+}
+
+/// 原地修改某个预设配置中的单个字段并保留 TOML 文件的注释/格式；预设以 JSON 存储时
+/// 返回错误（见 `FilterManager::set_config_value`）
+#[tauri::command]
+pub fn set_filter_config_value(
+    preset_id: String,
+    dotted_key: String,
+    value: serde_json::Value,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    let toml_value = preset_store::json_to_toml_value(value)?;
+    manager.set_config_value(&preset_id, &dotted_key, toml_value)
+}
+
 /// 删除预设
 #[tauri::command]
 pub fn delete_filter_preset(
     preset_id: String,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.delete_preset(&preset_id)
 }
@@ -91,54 +146,158 @@ pub fn rename_filter_preset(
     preset_id: String,
     new_name: String,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.rename_preset(&preset_id, new_name)
 }
 
+/// 获取当前的重置快捷键
+#[tauri::command]
+pub fn get_filter_reset_hotkey(state: State<FilterManagerState>) -> Result<Option<String>, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    Ok(manager.get_reset_hotkey())
+}
+
+/// 设置（或清除）重置快捷键
+#[tauri::command]
+pub fn set_filter_reset_hotkey(
+    hotkey: Option<String>,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.set_reset_hotkey(hotkey)
+}
+
+/// 获取所有多显示器配置方案
+#[tauri::command]
+pub fn get_all_monitor_profiles(
+    state: State<FilterManagerState>,
+) -> Result<Vec<MonitorProfile>, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    Ok(manager.get_all_profiles())
+}
+
+/// 创建多显示器配置方案
+#[tauri::command]
+pub fn create_monitor_profile(
+    name: String,
+    assignments: HashMap<String, MonitorProfileTarget>,
+    state: State<FilterManagerState>,
+) -> Result<String, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.create_profile(name, assignments)
+}
+
+/// 更新多显示器配置方案
+#[tauri::command]
+pub fn update_monitor_profile(
+    profile_id: String,
+    name: Option<String>,
+    assignments: Option<HashMap<String, MonitorProfileTarget>>,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.update_profile(&profile_id, name, assignments)
+}
+
+/// 删除多显示器配置方案
+#[tauri::command]
+pub fn delete_monitor_profile(
+    profile_id: String,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.delete_profile(&profile_id)
+}
+
+/// 应用多显示器配置方案：不同显示器按各自的映射应用各自的效果
+#[tauri::command]
+pub fn apply_monitor_profile(
+    profile_id: String,
+    transition_ms: Option<u64>,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.apply_profile(&profile_id, transition_ms)
+}
+
 /// 应用预设
+///
+/// `transition_ms` 可选，传入时会在该毫秒数内平滑过渡到预设效果，而不是瞬间切换
 #[tauri::command]
 pub fn apply_filter_preset(
     preset_id: String,
     monitor_ids: Vec<String>,
+    transition_ms: Option<u64>,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
-    manager.apply_preset(&preset_id, monitor_ids)
+    manager.apply_preset(&preset_id, monitor_ids, transition_ms)
 }
 
 /// 直接应用滤镜配置（用于实时预览，不保存）
+///
+/// `transition_ms` 可选，传入时会在该毫秒数内平滑过渡到目标效果，而不是瞬间切换
 #[tauri::command]
 pub fn apply_filter_config(
     config: FilterConfig,
     monitor_ids: Vec<String>,
+    transition_ms: Option<u64>,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
-    manager.apply_config(&config, monitor_ids)
+    manager.apply_config(&config, monitor_ids, transition_ms)
 }
 
 /// 获取当前激活的预设
 #[tauri::command]
 pub fn get_active_filter_preset(
     state: State<FilterManagerState>,
-) -> Result<Option<FilterPreset>, String> {
+) -> Result<Option<FilterPreset>, FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     Ok(manager.get_active_preset())
 }
@@ -147,11 +306,11 @@ pub fn get_active_filter_preset(
 #[tauri::command]
 pub fn reset_filter(
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.reset_filter()
 }
@@ -160,11 +319,11 @@ pub fn reset_filter(
 #[tauri::command]
 pub fn export_filter_presets(
     state: State<FilterManagerState>,
-) -> Result<String, String> {
+) -> Result<String, FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.export_presets()
 }
@@ -174,11 +333,11 @@ pub fn export_filter_presets(
 pub fn import_filter_presets(
     json: String,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.import_presets(&json)
 }
@@ -187,53 +346,109 @@ pub fn import_filter_presets(
 #[tauri::command]
 pub fn reset_filter_presets_to_defaults(
     state: State<FilterManagerState>,
-) -> Result<(), String> {
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
     manager.reset_to_defaults()
 }
 
+/// 获取当前定时表（日间/夜间自动切换等）
+#[tauri::command]
+pub fn get_schedule(state: State<FilterManagerState>) -> Result<Schedule, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    Ok(manager.get_schedule())
+}
+
+/// 设置（覆盖）定时表
+#[tauri::command]
+pub fn set_schedule(schedule: Schedule, state: State<FilterManagerState>) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.set_schedule(schedule)
+}
+
+/// 标记实时预览状态；预览中定时调度不会抢着切换预设
+#[tauri::command]
+pub fn set_schedule_preview_active(active: bool, state: State<FilterManagerState>) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.set_preview_active(active);
+    Ok(())
+}
+
+/// 查询指定显示器的 DDC/CI 能力（是否支持、当前亮度/对比度及其最大值）
+#[tauri::command]
+pub fn get_ddc_capabilities(
+    monitor_id: String,
+    state: State<FilterManagerState>,
+) -> Result<DdcCapabilities, FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    Ok(manager.get_ddc_capabilities(&monitor_id))
+}
+
+/// 直接设置指定显示器的硬件亮度（百分比，0-100），不经由预设/配置
+#[tauri::command]
+pub fn set_hardware_brightness(
+    monitor_id: String,
+    percent: u8,
+    state: State<FilterManagerState>,
+) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.set_hardware_brightness(&monitor_id, percent)
+}
+
 /// 刷新快捷键注册（在修改快捷键后调用）
 #[tauri::command]
 pub fn refresh_hotkey_registrations(
-    app: tauri::AppHandle,
     state: State<FilterManagerState>,
-) -> Result<(), String> {
-    use std::str::FromStr;
-    use tauri_plugin_global_shortcut::GlobalShortcutExt;
-
+) -> Result<(), FilterError> {
     let manager_lock = state.0.lock().unwrap();
     let manager = manager_lock
         .as_ref()
-        .ok_or_else(|| "滤镜管理器未初始化".to_string())?;
+        .ok_or(FilterError::NotInitialized)?;
 
-    // 获取所有预设
-    let presets = manager.get_all_presets();
+    manager.start_hotkeys()
+}
 
-    // 取消注册所有快捷键
-    println!("取消注册所有快捷键...");
-    if let Err(e) = app.global_shortcut().unregister_all() {
-        eprintln!("取消注册快捷键失败: {}", e);
-    }
+/// 启动全局快捷键监听
+#[tauri::command]
+pub fn start_filter_hotkeys(state: State<FilterManagerState>) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
 
-    // 重新注册每个预设的快捷键
-    println!("重新注册快捷键...");
-    for preset in presets {
-        if let Some(ref hotkey) = preset.hotkey {
-            match tauri_plugin_global_shortcut::Shortcut::from_str(hotkey) {
-                Ok(shortcut) => {
-                    match app.global_shortcut().register(shortcut) {
-                        Ok(_) => println!("已注册快捷键: {} -> {}", hotkey, preset.name),
-                        Err(e) => eprintln!("注册快捷键失败 {} ({}): {}", hotkey, preset.name, e),
-                    }
-                }
-                Err(e) => eprintln!("解析快捷键失败 {} ({}): {}", hotkey, preset.name, e),
-            }
-        }
-    }
+    manager.start_hotkeys()
+}
 
-    Ok(())
+/// 停止全局快捷键监听
+#[tauri::command]
+pub fn stop_filter_hotkeys(state: State<FilterManagerState>) -> Result<(), FilterError> {
+    let manager_lock = state.0.lock().unwrap();
+    let manager = manager_lock
+        .as_ref()
+        .ok_or(FilterError::NotInitialized)?;
+
+    manager.stop_hotkeys()
 }
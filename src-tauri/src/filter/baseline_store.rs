@@ -0,0 +1,104 @@
+// 持久化保存每个显示器"被 T2 接管前"的原始 Gamma Ramp，使用内嵌的 redb 数据库
+//
+// 纯内存缓存（`GammaRampController::original_ramps`）在进程崩溃后会丢失，届时用户将
+// 永远无法恢复到真实的系统基线（例如其他护眼软件或系统自带的夜间模式写入的 Ramp）。
+// 这里把第一次接触到的 Ramp 落盘，key 为显示器的 `device_name`。
+use crate::filter::gamma_ramp::GammaRamp;
+use redb::{Database, ReadableTable, TableDefinition};
+use std::path::Path;
+
+const RAMP_TABLE: TableDefinition<&str, &[u8]> = TableDefinition::new("original_gamma_ramps");
+
+/// 每个 u16 占 2 字节，红/绿/蓝各 256 项
+const RAMP_BYTES_LEN: usize = 256 * 2 * 3;
+
+pub struct BaselineStore {
+    db: Database,
+}
+
+impl BaselineStore {
+    /// 在配置目录下打开（或创建）基线数据库
+    pub fn open(config_dir: &Path) -> Result<Self, String> {
+        let db_path = config_dir.join("baseline_ramps.redb");
+        let db = Database::create(&db_path)
+            .map_err(|e| format!("无法打开基线 Gamma Ramp 数据库: {}", e))?;
+        Ok(Self { db })
+    }
+
+    /// 读取某个显示器保存的原始 Gamma Ramp，不存在则返回 `None`
+    pub fn get(&self, device_name: &str) -> Result<Option<GammaRamp>, String> {
+        let read_txn = self
+            .db
+            .begin_read()
+            .map_err(|e| format!("无法打开基线数据库读事务: {}", e))?;
+
+        let table = match read_txn.open_table(RAMP_TABLE) {
+            Ok(table) => table,
+            Err(redb::TableError::TableDoesNotExist(_)) => return Ok(None),
+            Err(e) => return Err(format!("无法打开基线数据表: {}", e)),
+        };
+
+        match table.get(device_name) {
+            Ok(Some(bytes)) => decode_ramp(bytes.value()).map(Some),
+            Ok(None) => Ok(None),
+            Err(e) => Err(format!("读取基线 Gamma Ramp 失败: {}", e)),
+        }
+    }
+
+    /// 仅当该显示器尚未保存过基线时才写入，避免在滤镜已激活时把我们自己的输出当成基线存下
+    pub fn set_if_absent(&self, device_name: &str, ramp: &GammaRamp) -> Result<(), String> {
+        if self.get(device_name)?.is_some() {
+            return Ok(());
+        }
+
+        let write_txn = self
+            .db
+            .begin_write()
+            .map_err(|e| format!("无法打开基线数据库写事务: {}", e))?;
+
+        {
+            let mut table = write_txn
+                .open_table(RAMP_TABLE)
+                .map_err(|e| format!("无法打开基线数据表: {}", e))?;
+            table
+                .insert(device_name, encode_ramp(ramp).as_slice())
+                .map_err(|e| format!("写入基线 Gamma Ramp 失败: {}", e))?;
+        }
+
+        write_txn
+            .commit()
+            .map_err(|e| format!("提交基线数据库事务失败: {}", e))
+    }
+}
+
+fn encode_ramp(ramp: &GammaRamp) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(RAMP_BYTES_LEN);
+    for channel in [&ramp.red, &ramp.green, &ramp.blue] {
+        for value in channel {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    bytes
+}
+
+fn decode_ramp(bytes: &[u8]) -> Result<GammaRamp, String> {
+    if bytes.len() != RAMP_BYTES_LEN {
+        return Err(format!(
+            "基线 Gamma Ramp 数据长度异常: 期望 {} 字节，实际 {} 字节",
+            RAMP_BYTES_LEN,
+            bytes.len()
+        ));
+    }
+
+    let mut ramp = GammaRamp::default();
+    let channels = [&mut ramp.red, &mut ramp.green, &mut ramp.blue];
+    let mut offset = 0;
+    for channel in channels {
+        for value in channel.iter_mut() {
+            *value = u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+            offset += 2;
+        }
+    }
+
+    Ok(ramp)
+}
@@ -0,0 +1,341 @@
+// DDC/CI 硬件亮度/对比度控制：相比 Gamma Ramp 只是重新映射 LUT，
+// DDC/CI 直接驱动显示器背光/对比度电路，可以真正压暗一块很亮的面板
+#[cfg(target_os = "windows")]
+use windows::Win32::Devices::Display::{
+    DestroyPhysicalMonitors, GetNumberOfPhysicalMonitorsFromHMONITOR,
+    GetPhysicalMonitorsFromHMONITOR, GetVCPFeatureAndVCPFeatureReply, SetVCPFeature,
+    PHYSICAL_MONITOR,
+};
+#[cfg(target_os = "windows")]
+use windows::Win32::Foundation::{BOOL, LPARAM, RECT};
+#[cfg(target_os = "windows")]
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors, GetMonitorInfoW, HDC, HMONITOR, MONITORINFOEXW,
+};
+use serde::{Deserialize, Serialize};
+
+/// VCP 代码：亮度（luminance）
+#[cfg(target_os = "windows")]
+const VCP_BRIGHTNESS: u8 = 0x10;
+/// VCP 代码：对比度
+#[cfg(target_os = "windows")]
+const VCP_CONTRAST: u8 = 0x12;
+
+/// 枚举系统中所有物理显示器句柄（不检测各显示器是否实际响应 DDC/CI）
+///
+/// 通过 `EnumDisplayMonitors` 拿到每个 `HMONITOR`，再用
+/// `GetPhysicalMonitorsFromHMONITOR` 取出其下的物理显示器句柄，按发现顺序拼接成一个列表，
+/// `HardwareMonitorController` 的 `monitor_index` 即为这个列表中的下标
+#[cfg(target_os = "windows")]
+fn enumerate_physical_monitors() -> Result<Vec<PHYSICAL_MONITOR>, String> {
+    unsafe extern "system" fn collect_callback(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        lparam: LPARAM,
+    ) -> BOOL {
+        let handles = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+        handles.push(hmonitor);
+        BOOL(1)
+    }
+
+    let mut hmonitors: Vec<HMONITOR> = Vec::new();
+    let success = unsafe {
+        EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect_callback),
+            LPARAM(&mut hmonitors as *mut _ as isize),
+        )
+    };
+
+    if !success.as_bool() {
+        return Err("枚举显示器句柄失败".to_string());
+    }
+
+    let mut physical_monitors = Vec::new();
+
+    for hmonitor in hmonitors {
+        let mut count: u32 = 0;
+        let got_count = unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, &mut count) };
+        if got_count.is_err() || count == 0 {
+            continue;
+        }
+
+        let mut monitors = vec![PHYSICAL_MONITOR::default(); count as usize];
+        let got_monitors = unsafe { GetPhysicalMonitorsFromHMONITOR(hmonitor, &mut monitors) };
+        if got_monitors.is_err() {
+            continue;
+        }
+
+        physical_monitors.extend(monitors);
+    }
+
+    Ok(physical_monitors)
+}
+
+/// 查找 `device_name`（`EnumDisplayDevicesW` 中的适配器设备名，如 `\\.\DISPLAY1`）
+/// 对应的物理显示器句柄，用于按显示器而不是按全局下标做 DDC/CI 操作
+///
+/// 调用方用完后需要自行通过 `DestroyPhysicalMonitors` 释放返回的句柄
+#[cfg(target_os = "windows")]
+fn find_physical_monitor_for_device(device_name: &str) -> Option<PHYSICAL_MONITOR> {
+    unsafe extern "system" fn collect_callback(
+        hmonitor: HMONITOR,
+        _hdc: HDC,
+        _rect: *mut RECT,
+        lparam: LPARAM,
+    ) -> BOOL {
+        let handles = &mut *(lparam.0 as *mut Vec<HMONITOR>);
+        handles.push(hmonitor);
+        BOOL(1)
+    }
+
+    let mut hmonitors: Vec<HMONITOR> = Vec::new();
+    let success = unsafe {
+        EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(collect_callback),
+            LPARAM(&mut hmonitors as *mut _ as isize),
+        )
+    };
+
+    if !success.as_bool() {
+        return None;
+    }
+
+    for hmonitor in hmonitors {
+        let mut info: MONITORINFOEXW = unsafe { std::mem::zeroed() };
+        info.monitorInfo.cbSize = std::mem::size_of::<MONITORINFOEXW>() as u32;
+
+        let got_info = unsafe { GetMonitorInfoW(hmonitor, &mut info.monitorInfo) };
+        if !got_info.as_bool() {
+            continue;
+        }
+
+        let len = info.szDevice.iter().position(|&c| c == 0).unwrap_or(info.szDevice.len());
+        let name = String::from_utf16_lossy(&info.szDevice[..len]);
+
+        if name != device_name {
+            continue;
+        }
+
+        let mut count: u32 = 0;
+        if unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(hmonitor, &mut count) }.is_err() || count == 0 {
+            return None;
+        }
+
+        let mut monitors = vec![PHYSICAL_MONITOR::default(); count as usize];
+        if unsafe { GetPhysicalMonitorsFromHMONITOR(hmonitor, &mut monitors) }.is_err() {
+            return None;
+        }
+
+        return monitors.into_iter().next();
+    }
+
+    None
+}
+
+/// 按显示器 DDC/CI 能力查询的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DdcCapabilities {
+    pub supports_ddc: bool,
+    /// (当前值, 最大值)
+    pub brightness: Option<(u32, u32)>,
+    /// (当前值, 最大值)
+    pub contrast: Option<(u32, u32)>,
+}
+
+/// 检测指定显示器是否支持 DDC/CI（尝试读取其亮度 VCP 特性）
+#[cfg(target_os = "windows")]
+pub fn supports_ddc(device_name: &str) -> bool {
+    get_ddc_capabilities(device_name).supports_ddc
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn supports_ddc(_device_name: &str) -> bool {
+    false
+}
+
+/// 查询指定显示器的 DDC/CI 能力与当前亮度/对比度
+#[cfg(target_os = "windows")]
+pub fn get_ddc_capabilities(device_name: &str) -> DdcCapabilities {
+    let monitor = match find_physical_monitor_for_device(device_name) {
+        Some(m) => m,
+        None => {
+            return DdcCapabilities {
+                supports_ddc: false,
+                brightness: None,
+                contrast: None,
+            }
+        }
+    };
+
+    let brightness = unsafe {
+        let mut current = 0u32;
+        let mut max = 0u32;
+        GetVCPFeatureAndVCPFeatureReply(monitor.hPhysicalMonitor, VCP_BRIGHTNESS, None, &mut current, &mut max)
+            .map(|_| (current, max))
+            .ok()
+    };
+
+    let contrast = unsafe {
+        let mut current = 0u32;
+        let mut max = 0u32;
+        GetVCPFeatureAndVCPFeatureReply(monitor.hPhysicalMonitor, VCP_CONTRAST, None, &mut current, &mut max)
+            .map(|_| (current, max))
+            .ok()
+    };
+
+    let mut handles = vec![monitor];
+    unsafe {
+        let _ = DestroyPhysicalMonitors(&mut handles);
+    }
+
+    DdcCapabilities {
+        supports_ddc: brightness.is_some(),
+        brightness,
+        contrast,
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn get_ddc_capabilities(_device_name: &str) -> DdcCapabilities {
+    DdcCapabilities {
+        supports_ddc: false,
+        brightness: None,
+        contrast: None,
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn with_physical_monitor<T>(
+    monitor_index: usize,
+    f: impl FnOnce(&PHYSICAL_MONITOR) -> Result<T, String>,
+) -> Result<T, String> {
+    let monitors = enumerate_physical_monitors()?;
+
+    let monitor = monitors
+        .get(monitor_index)
+        .ok_or_else(|| format!("物理显示器索引 {} 不存在或不支持 DDC/CI", monitor_index))?;
+
+    let result = f(monitor);
+
+    let mut handles = monitors;
+    unsafe {
+        let _ = DestroyPhysicalMonitors(&mut handles);
+    }
+
+    result
+}
+
+/// DDC/CI 硬件亮度/对比度控制器
+pub struct HardwareMonitorController;
+
+impl HardwareMonitorController {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// 设置物理显示器的硬件亮度（百分比，VCP 0x10）
+    #[cfg(target_os = "windows")]
+    pub fn set_brightness(&self, monitor_index: usize, percent: u32) -> Result<(), String> {
+        with_physical_monitor(monitor_index, |monitor| unsafe {
+            SetVCPFeature(monitor.hPhysicalMonitor, VCP_BRIGHTNESS, percent)
+                .map_err(|e| format!("设置硬件亮度失败: {}", e))
+        })
+    }
+
+    /// 读取物理显示器当前的硬件亮度，返回 (当前值, 最大值)（VCP 0x10）
+    #[cfg(target_os = "windows")]
+    pub fn get_brightness(&self, monitor_index: usize) -> Result<(u32, u32), String> {
+        with_physical_monitor(monitor_index, |monitor| unsafe {
+            let mut current = 0u32;
+            let mut max = 0u32;
+            GetVCPFeatureAndVCPFeatureReply(
+                monitor.hPhysicalMonitor,
+                VCP_BRIGHTNESS,
+                None,
+                &mut current,
+                &mut max,
+            )
+            .map_err(|e| format!("读取硬件亮度失败: {}", e))?;
+            Ok((current, max))
+        })
+    }
+
+    /// 设置物理显示器的硬件对比度（VCP 0x12）
+    #[cfg(target_os = "windows")]
+    pub fn set_contrast(&self, monitor_index: usize, value: u32) -> Result<(), String> {
+        with_physical_monitor(monitor_index, |monitor| unsafe {
+            SetVCPFeature(monitor.hPhysicalMonitor, VCP_CONTRAST, value)
+                .map_err(|e| format!("设置硬件对比度失败: {}", e))
+        })
+    }
+
+    /// 读取物理显示器当前的硬件对比度，返回 (当前值, 最大值)（VCP 0x12）
+    #[cfg(target_os = "windows")]
+    pub fn get_contrast(&self, monitor_index: usize) -> Result<(u32, u32), String> {
+        with_physical_monitor(monitor_index, |monitor| unsafe {
+            let mut current = 0u32;
+            let mut max = 0u32;
+            GetVCPFeatureAndVCPFeatureReply(
+                monitor.hPhysicalMonitor,
+                VCP_CONTRAST,
+                None,
+                &mut current,
+                &mut max,
+            )
+            .map_err(|e| format!("读取硬件对比度失败: {}", e))?;
+            Ok((current, max))
+        })
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_brightness(&self, _monitor_index: usize, _percent: u32) -> Result<(), String> {
+        Err("硬件亮度控制仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn get_brightness(&self, _monitor_index: usize) -> Result<(u32, u32), String> {
+        Err("硬件亮度控制仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_contrast(&self, _monitor_index: usize, _value: u32) -> Result<(), String> {
+        Err("硬件对比度控制仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn get_contrast(&self, _monitor_index: usize) -> Result<(u32, u32), String> {
+        Err("硬件对比度控制仅支持 Windows 平台".to_string())
+    }
+
+    /// 按显示器设备名（而不是全局物理显示器下标）设置硬件亮度百分比
+    ///
+    /// 供 `FilterManager` 在应用滤镜时使用：它手上拿到的是 `device_name`，
+    /// 而不是 `enumerate_physical_monitors` 返回列表中的下标
+    #[cfg(target_os = "windows")]
+    pub fn set_brightness_for_device(&self, device_name: &str, percent: u32) -> Result<(), String> {
+        let monitor = find_physical_monitor_for_device(device_name)
+            .ok_or_else(|| format!("显示器 {} 不支持 DDC/CI", device_name))?;
+
+        let result = unsafe {
+            SetVCPFeature(monitor.hPhysicalMonitor, VCP_BRIGHTNESS, percent)
+                .map_err(|e| format!("设置硬件亮度失败: {}", e))
+        };
+
+        let mut handles = vec![monitor];
+        unsafe {
+            let _ = DestroyPhysicalMonitors(&mut handles);
+        }
+
+        result
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_brightness_for_device(&self, _device_name: &str, _percent: u32) -> Result<(), String> {
+        Err("硬件亮度控制仅支持 Windows 平台".to_string())
+    }
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+
+/// 滤镜模块的结构化错误类型
+///
+/// 相比到处传递的 `Result<_, String>`，这里额外带上一个稳定的数字错误码（`code()`），
+/// 不会随着文案调整或本地化而变化，前端可以据此分支处理（例如区分"快捷键冲突"和
+/// "预设不存在"），而不必解析本地化后的错误文案；`Display` 仍然产出现有的中文提示
+#[derive(Debug)]
+pub enum FilterError {
+    /// 滤镜管理器尚未初始化成功
+    NotInitialized,
+    /// 无法创建配置目录
+    ConfigDirCreate(String),
+    /// 无法读取配置文件
+    ConfigRead(String),
+    /// 配置文件内容无法解析
+    ConfigParse(String),
+    /// 配置序列化失败
+    Serialize(String),
+    /// 预设不存在
+    PresetNotFound(String),
+    /// 快捷键与其他预设冲突
+    HotkeyConflict(String),
+    /// 滤镜配置参数不合法
+    InvalidConfig(String),
+    /// 未选择任何显示器
+    NoMonitorSelected,
+    /// 应用滤镜到显示器失败
+    MonitorApplyFailed(String),
+}
+
+impl FilterError {
+    /// 稳定的数字错误码，不随文案调整或本地化变化
+    pub fn code(&self) -> u32 {
+        match self {
+            FilterError::NotInitialized => 1000,
+            FilterError::ConfigDirCreate(_) => 1001,
+            FilterError::ConfigRead(_) => 1002,
+            FilterError::ConfigParse(_) => 1003,
+            FilterError::Serialize(_) => 1004,
+            FilterError::PresetNotFound(_) => 1005,
+            FilterError::HotkeyConflict(_) => 1006,
+            FilterError::InvalidConfig(_) => 1007,
+            FilterError::NoMonitorSelected => 1008,
+            FilterError::MonitorApplyFailed(_) => 1009,
+        }
+    }
+}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::NotInitialized => write!(f, "滤镜管理器未初始化"),
+            FilterError::ConfigDirCreate(detail) => write!(f, "无法创建配置目录: {}", detail),
+            FilterError::ConfigRead(detail) => write!(f, "无法读取配置文件: {}", detail),
+            FilterError::ConfigParse(detail) => write!(f, "配置文件格式错误: {}", detail),
+            FilterError::Serialize(detail) => write!(f, "无法序列化配置: {}", detail),
+            FilterError::PresetNotFound(preset_id) => write!(f, "预设不存在: {}", preset_id),
+            FilterError::HotkeyConflict(detail) => write!(f, "{}", detail),
+            FilterError::InvalidConfig(detail) => write!(f, "{}", detail),
+            FilterError::NoMonitorSelected => write!(f, "未选择任何显示器"),
+            FilterError::MonitorApplyFailed(detail) => write!(f, "应用滤镜失败: {}", detail),
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+/// 序列化为 `{ code, message }`，发给前端后既可以按 `code` 分支判断，也可以直接展示 `message`
+impl serde::Serialize for FilterError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let mut state = serializer.serialize_struct("FilterError", 2)?;
+        state.serialize_field("code", &self.code())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_is_stable_per_variant() {
+        assert_eq!(FilterError::PresetNotFound("x".to_string()).code(), 1005);
+        assert_eq!(FilterError::NoMonitorSelected.code(), 1008);
+    }
+
+    #[test]
+    fn test_display_matches_existing_wording() {
+        let err = FilterError::PresetNotFound("custom_1".to_string());
+        assert_eq!(err.to_string(), "预设不存在: custom_1");
+    }
+}
@@ -1,3 +1,4 @@
+use crate::filter::baseline_store::BaselineStore;
 use crate::filter::types::FilterConfig;
 use std::collections::HashMap;
 
@@ -54,27 +55,135 @@ impl GammaRamp {
             blue: [0; 256],
         };
 
+        let (temp_r, temp_g, temp_b) = config.temperature_multipliers();
+
         for i in 0..256 {
-            ramp.red[i] = config.calculate_color_value(config.red_scale, i);
-            ramp.green[i] = config.calculate_color_value(config.green_scale, i);
-            ramp.blue[i] = config.calculate_color_value(config.blue_scale, i);
+            ramp.red[i] = config.calculate_color_value(config.red_scale * temp_r, i);
+            ramp.green[i] = config.calculate_color_value(config.green_scale * temp_g, i);
+            ramp.blue[i] = config.calculate_color_value(config.blue_scale * temp_b, i);
         }
 
         ramp
     }
 }
 
+/// 读取指定显示器当前的 Gamma Ramp（不依赖 `GammaRampController` 实例，供后台过渡线程调用）
+#[cfg(target_os = "windows")]
+fn read_ramp_raw(monitor_device: &str) -> Result<GammaRamp, String> {
+    unsafe {
+        // 将设备名转换为 UTF-16
+        let device_name: Vec<u16> = monitor_device.encode_utf16().chain(std::iter::once(0)).collect();
+
+        // 创建设备上下文
+        let hdc = CreateDCW(
+            PCWSTR(device_name.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            None,
+        );
+
+        if hdc.is_invalid() {
+            return Err(format!("无法为显示器 {} 创建设备上下文", monitor_device));
+        }
+
+        let mut ramp = GammaRamp::default();
+        let result = GetDeviceGammaRamp(
+            hdc,
+            &mut ramp as *mut GammaRamp as *mut _,
+        );
+
+        // 释放设备上下文
+        let _ = DeleteDC(hdc);
+
+        if result.as_bool() {
+            Ok(ramp)
+        } else {
+            Err(format!("无法获取显示器 {} 的 Gamma Ramp", monitor_device))
+        }
+    }
+}
+
+/// 为指定显示器写入 Gamma Ramp（不依赖 `GammaRampController` 实例，供后台过渡线程调用）
+#[cfg(target_os = "windows")]
+fn write_ramp_raw(ramp: &GammaRamp, monitor_device: &str) -> Result<(), String> {
+    unsafe {
+        // 将设备名转换为 UTF-16
+        let device_name: Vec<u16> = monitor_device.encode_utf16().chain(std::iter::once(0)).collect();
+
+        // 创建设备上下文
+        let hdc = CreateDCW(
+            PCWSTR(device_name.as_ptr()),
+            PCWSTR::null(),
+            PCWSTR::null(),
+            None,
+        );
+
+        if hdc.is_invalid() {
+            return Err(format!("无法为显示器 {} 创建设备上下文", monitor_device));
+        }
+
+        // 设置 Gamma Ramp
+        let result = SetDeviceGammaRamp(
+            hdc,
+            ramp as *const GammaRamp as *const _,
+        );
+
+        // 释放设备上下文
+        let _ = DeleteDC(hdc);
+
+        if result.as_bool() {
+            Ok(())
+        } else {
+            Err(format!("无法为显示器 {} 设置 Gamma Ramp", monitor_device))
+        }
+    }
+}
+
+/// 在 `start` 与 `target` 之间按比例 `t` (0.0-1.0) 线性插值
+#[cfg(target_os = "windows")]
+fn lerp_u16(start: u16, target: u16, t: f64) -> u16 {
+    (start as f64 + (target as f64 - start as f64) * t).round() as u16
+}
+
 /// Gamma Ramp 控制器
 pub struct GammaRampController {
     #[cfg(target_os = "windows")]
     original_ramps: HashMap<String, GammaRamp>,
+
+    /// 过渡代次计数器：每次发起新的平滑过渡都会递增，正在运行的过渡线程
+    /// 发现代次已变化就立即停止，从而让"快速切换预设"不会叠加出半吊子的过渡状态
+    #[cfg(target_os = "windows")]
+    transition_generation: std::sync::Arc<std::sync::atomic::AtomicU64>,
+
+    /// 持久化的原始 Gamma Ramp 基线；`None` 表示数据库打开失败，此时退化为纯内存行为
+    #[cfg(target_os = "windows")]
+    baseline_store: Option<BaselineStore>,
 }
 
 impl GammaRampController {
-    pub fn new() -> Self {
+    /// 创建控制器，并尝试在 `config_dir` 下打开基线数据库
+    ///
+    /// 数据库打开失败不会导致构造失败（多数情况下仅仅是首次基线读取变为纯内存、不可跨进程恢复），
+    /// 只会打印一条警告
+    pub fn new(config_dir: &std::path::Path) -> Self {
+        let _ = config_dir;
+
+        #[cfg(target_os = "windows")]
+        let baseline_store = match BaselineStore::open(config_dir) {
+            Ok(store) => Some(store),
+            Err(e) => {
+                eprintln!("警告：无法打开基线 Gamma Ramp 数据库，原始状态恢复将仅限本次运行: {}", e);
+                None
+            }
+        };
+
         Self {
             #[cfg(target_os = "windows")]
             original_ramps: HashMap::new(),
+            #[cfg(target_os = "windows")]
+            transition_generation: std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0)),
+            #[cfg(target_os = "windows")]
+            baseline_store,
         }
     }
 
@@ -96,7 +205,7 @@ impl GammaRampController {
         for monitor in monitors {
             // 保存原始 Gamma Ramp（仅第一次）
             if !self.original_ramps.contains_key(&monitor.device_name) {
-                match self.get_ramp_for_monitor(&monitor.device_name) {
+                match self.capture_baseline(&monitor.device_name) {
                     Ok(ramp) => {
                         self.original_ramps.insert(monitor.device_name.clone(), ramp);
                     }
@@ -131,8 +240,13 @@ impl GammaRampController {
     /// 重置到原始状态
     #[cfg(target_os = "windows")]
     pub fn reset(&mut self) -> Result<(), String> {
+        // 先让代次失效，这样任何仍在后台跑的 run_transition 线程会在下一帧检查时发现
+        // 代次不匹配并退出，不会在下面的即时恢复之后又写入一次过渡中的旧值
+        self.transition_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
         let mut errors = Vec::new();
-        
+
         // 遍历保存的原始 ramp 并恢复
         // 注意：这里我们需要 clone keys 来避免借用检查问题，或者使用迭代器
         let devices: Vec<String> = self.original_ramps.keys().cloned().collect();
@@ -157,76 +271,40 @@ impl GammaRampController {
         Ok(())
     }
 
-    /// 获取指定显示器的 Gamma Ramp
+    /// 获取某个显示器"被 T2 接管前"的基线 Gamma Ramp
+    ///
+    /// 优先复用持久化数据库中已保存的基线（即便本进程刚启动、内存缓存为空，也不会
+    /// 把此刻可能已经是我们自己滤镜输出的 Ramp 误当成基线）；只有数据库里也没有时，
+    /// 才真正读取硬件当前 Ramp 并写入数据库
     #[cfg(target_os = "windows")]
-    fn get_ramp_for_monitor(&self, monitor_device: &str) -> Result<GammaRamp, String> {
-        unsafe {
-            // 将设备名转换为 UTF-16
-            let device_name: Vec<u16> = monitor_device.encode_utf16().chain(std::iter::once(0)).collect();
-
-            // 创建设备上下文
-            let hdc = CreateDCW(
-                PCWSTR(device_name.as_ptr()),
-                PCWSTR::null(),
-                PCWSTR::null(),
-                None,
-            );
-
-            if hdc.is_invalid() {
-                return Err(format!("无法为显示器 {} 创建设备上下文", monitor_device));
+    fn capture_baseline(&self, monitor_device: &str) -> Result<GammaRamp, String> {
+        if let Some(store) = &self.baseline_store {
+            if let Ok(Some(saved)) = store.get(monitor_device) {
+                return Ok(saved);
             }
+        }
 
-            let mut ramp = GammaRamp::default();
-            let result = GetDeviceGammaRamp(
-                hdc,
-                &mut ramp as *mut GammaRamp as *mut _,
-            );
-
-            // 释放设备上下文
-            let _ = DeleteDC(hdc);
+        let current = self.get_ramp_for_monitor(monitor_device)?;
 
-            if result.as_bool() {
-                Ok(ramp)
-            } else {
-                Err(format!("无法获取显示器 {} 的 Gamma Ramp", monitor_device))
+        if let Some(store) = &self.baseline_store {
+            if let Err(e) = store.set_if_absent(monitor_device, &current) {
+                eprintln!("警告：无法持久化显示器 {} 的基线 Gamma Ramp: {}", monitor_device, e);
             }
         }
+
+        Ok(current)
+    }
+
+    /// 获取指定显示器的 Gamma Ramp
+    #[cfg(target_os = "windows")]
+    fn get_ramp_for_monitor(&self, monitor_device: &str) -> Result<GammaRamp, String> {
+        read_ramp_raw(monitor_device)
     }
 
     /// 为指定显示器设置 Gamma Ramp
     #[cfg(target_os = "windows")]
     fn set_ramp_for_monitor(&self, ramp: &GammaRamp, monitor_device: &str) -> Result<(), String> {
-        unsafe {
-            // 将设备名转换为 UTF-16
-            let device_name: Vec<u16> = monitor_device.encode_utf16().chain(std::iter::once(0)).collect();
-
-            // 创建设备上下文
-            let hdc = CreateDCW(
-                PCWSTR(device_name.as_ptr()),
-                PCWSTR::null(),
-                PCWSTR::null(),
-                None,
-            );
-
-            if hdc.is_invalid() {
-                return Err(format!("无法为显示器 {} 创建设备上下文", monitor_device));
-            }
-
-            // 设置 Gamma Ramp
-            let result = SetDeviceGammaRamp(
-                hdc,
-                ramp as *const GammaRamp as *const _,
-            );
-
-            // 释放设备上下文
-            let _ = DeleteDC(hdc);
-
-            if result.as_bool() {
-                Ok(())
-            } else {
-                Err(format!("无法为显示器 {} 设置 Gamma Ramp", monitor_device))
-            }
-        }
+        write_ramp_raw(ramp, monitor_device)
     }
 
     /// 应用滤镜到指定显示器 (保留接口，但内部实现已统一)
@@ -237,14 +315,14 @@ impl GammaRampController {
         
         // 保存原始 ramp
         if !self.original_ramps.contains_key(monitor_device) {
-             match self.get_ramp_for_monitor(monitor_device) {
-                Ok(ramp) => {
-                    self.original_ramps.insert(monitor_device.to_string(), ramp);
-                }
-                Err(e) => return Err(e),
-            }
+            let ramp = self.capture_baseline(monitor_device)?;
+            self.original_ramps.insert(monitor_device.to_string(), ramp);
         }
 
+        // 立即应用同样会取代仍在进行中的平滑过渡，否则后台线程会在之后的帧里覆盖这次写入
+        self.transition_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+
         // 生成新的 Gamma Ramp
         let ramp = GammaRamp::from_config(config);
 
@@ -262,12 +340,214 @@ impl GammaRampController {
     #[cfg(target_os = "windows")]
     pub fn reset_monitor(&mut self, monitor_device: &str) -> Result<(), String> {
         if let Some(original) = self.original_ramps.get(monitor_device) {
-            self.set_ramp_for_monitor(original, monitor_device)
-        } else {
-            // 如果没有原始记录，尝试设置默认线性 ramp
-            let default_ramp = GammaRamp::default();
-            self.set_ramp_for_monitor(&default_ramp, monitor_device)
+            return self.set_ramp_for_monitor(original, monitor_device);
+        }
+
+        // 内存中没有记录，再查一次持久化基线，而不是直接退化为线性 Ramp
+        if let Some(store) = &self.baseline_store {
+            if let Ok(Some(original)) = store.get(monitor_device) {
+                return self.set_ramp_for_monitor(&original, monitor_device);
+            }
+        }
+
+        // 确实没有任何基线记录，只能退化为默认线性 ramp
+        let default_ramp = GammaRamp::default();
+        self.set_ramp_for_monitor(&default_ramp, monitor_device)
+    }
+
+    /// 在 `duration_ms` 毫秒内平滑过渡到 `config` 对应的 Gamma Ramp
+    ///
+    /// 从每个显示器"当前正在显示"的 Ramp（而不是已保存的原始基线）开始插值，
+    /// 这样连续快速切换预设时，新过渡总是从屏幕上真实显示的状态继续，不会跳变
+    #[cfg(target_os = "windows")]
+    pub fn apply_filter_smooth(&mut self, config: &FilterConfig, duration_ms: u64) -> Result<(), String> {
+        config.validate()?;
+
+        let monitors = crate::filter::monitor::enumerate_monitors()?;
+        if monitors.is_empty() {
+            return Err("未找到任何显示器".to_string());
+        }
+
+        let target = GammaRamp::from_config(config);
+        let mut steps = Vec::new();
+
+        for monitor in monitors {
+            if !self.original_ramps.contains_key(&monitor.device_name) {
+                if let Ok(ramp) = self.capture_baseline(&monitor.device_name) {
+                    self.original_ramps.insert(monitor.device_name.clone(), ramp);
+                }
+            }
+
+            let current = self
+                .get_ramp_for_monitor(&monitor.device_name)
+                .unwrap_or_else(|_| GammaRamp::default());
+
+            steps.push((monitor.device_name, current, target.clone()));
+        }
+
+        self.run_transition(steps, duration_ms);
+        Ok(())
+    }
+
+    /// 在 `duration_ms` 毫秒内平滑过渡回每个显示器保存的原始 Gamma Ramp
+    #[cfg(target_os = "windows")]
+    pub fn reset_smooth(&mut self, duration_ms: u64) -> Result<(), String> {
+        let devices: Vec<String> = self.original_ramps.keys().cloned().collect();
+        if devices.is_empty() {
+            return Ok(());
+        }
+
+        let mut steps = Vec::new();
+        for device in devices {
+            let current = self
+                .get_ramp_for_monitor(&device)
+                .unwrap_or_else(|_| GammaRamp::default());
+            if let Some(original) = self.original_ramps.get(&device).cloned() {
+                steps.push((device, current, original));
+            }
         }
+
+        self.original_ramps.clear();
+        self.run_transition(steps, duration_ms);
+        Ok(())
+    }
+
+    /// 启动（或取代）一次后台平滑过渡
+    ///
+    /// 递增 `transition_generation` 并把新值带入后台线程；线程每帧检查代次是否仍是自己的，
+    /// 一旦被新的 apply/reset 调用取代就立即退出，绝不会有两个过渡线程同时写同一块显示器
+    #[cfg(target_os = "windows")]
+    fn run_transition(&self, steps: Vec<(String, GammaRamp, GammaRamp)>, duration_ms: u64) {
+        if steps.is_empty() {
+            return;
+        }
+
+        let generation = self
+            .transition_generation
+            .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+            + 1;
+        let generation_flag = std::sync::Arc::clone(&self.transition_generation);
+
+        let total_steps = ((duration_ms as f64 / 1000.0) * 60.0).round().max(1.0) as u64;
+
+        std::thread::spawn(move || {
+            for step in 1..=total_steps {
+                if generation_flag.load(std::sync::atomic::Ordering::SeqCst) != generation {
+                    return;
+                }
+
+                let t = step as f64 / total_steps as f64;
+
+                for (device, start, target) in &steps {
+                    let mut ramp = GammaRamp::default();
+                    for i in 0..256 {
+                        ramp.red[i] = lerp_u16(start.red[i], target.red[i], t);
+                        ramp.green[i] = lerp_u16(start.green[i], target.green[i], t);
+                        ramp.blue[i] = lerp_u16(start.blue[i], target.blue[i], t);
+                    }
+                    let _ = write_ramp_raw(&ramp, device);
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(1000 / 60));
+            }
+        });
+    }
+
+    /// 对调用方选中的一组显示器执行平滑过渡（区别于 `apply_filter_smooth`：
+    /// 只作用于 `monitor_devices` 中的显示器，供 `FilterManager::apply_preset`/`apply_config`
+    /// 在需要动画过渡时调用，而不是全部枚举到的显示器）
+    #[cfg(target_os = "windows")]
+    pub fn apply_to_monitors_smooth(
+        &mut self,
+        config: &FilterConfig,
+        monitor_devices: &[String],
+        duration_ms: u64,
+    ) -> Result<(), String> {
+        config.validate()?;
+
+        if monitor_devices.is_empty() {
+            return Err("未选择任何显示器".to_string());
+        }
+
+        let target = GammaRamp::from_config(config);
+        let mut steps = Vec::new();
+
+        for device in monitor_devices {
+            if !self.original_ramps.contains_key(device) {
+                if let Ok(ramp) = self.capture_baseline(device) {
+                    self.original_ramps.insert(device.clone(), ramp);
+                }
+            }
+
+            let current = self
+                .get_ramp_for_monitor(device)
+                .unwrap_or_else(|_| GammaRamp::default());
+            steps.push((device.clone(), current, target.clone()));
+        }
+
+        self.run_transition(steps, duration_ms);
+        Ok(())
+    }
+
+    /// 估算显示器当前 Gamma Ramp 对应的色温（开尔文）
+    ///
+    /// 读取显示器当前的 Gamma Ramp，取红/蓝通道末端比值，在黑体近似曲线上二分查找
+    /// 最接近的色温，供 UI 展示"当前显示器大致处于多少 K"
+    #[cfg(target_os = "windows")]
+    pub fn estimate_current_temperature(&self, monitor_device: &str) -> Result<u32, String> {
+        let ramp = self.get_ramp_for_monitor(monitor_device)?;
+
+        if ramp.blue[255] == 0 {
+            return Err("无法估算色温：蓝色通道末端值为 0".to_string());
+        }
+
+        let ratio = ramp.red[255] as f64 / ramp.blue[255] as f64;
+
+        let mut low = 1000u32;
+        let mut high = 10000u32;
+
+        for _ in 0..32 {
+            let mid = (low + high) / 2;
+            let (r, _g, b) = crate::filter::types::blackbody_rgb(mid);
+            let mid_ratio = if b == 0.0 { f64::MAX } else { r / b };
+
+            if mid_ratio > ratio {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok((low + high) / 2)
+    }
+
+    /// 在软件层面渲染滤镜应用后的预览图，不写入任何硬件 Gamma Ramp
+    ///
+    /// 截取 `monitor_device` 当前画面，套用与 `GammaRamp::from_config` 相同的颜色变换，
+    /// 再按 `scale` 缩小分辨率，便于 UI 快速展示一张预览图
+    pub fn render_preview(
+        config: &FilterConfig,
+        monitor_device: &str,
+        scale: f32,
+    ) -> Result<image::RgbaImage, String> {
+        config.validate().map_err(|e| e.to_string())?;
+
+        let captured = crate::filter::preview::capture_monitor_rgba(monitor_device)?;
+        let filtered = crate::filter::preview::apply_config_to_image(&captured, config);
+
+        if scale <= 0.0 || scale >= 1.0 {
+            return Ok(filtered);
+        }
+
+        let new_width = ((filtered.width() as f32) * scale).round().max(1.0) as u32;
+        let new_height = ((filtered.height() as f32) * scale).round().max(1.0) as u32;
+
+        Ok(image::imageops::resize(
+            &filtered,
+            new_width,
+            new_height,
+            image::imageops::FilterType::Triangle,
+        ))
     }
 
     /// 非 Windows 平台的占位实现
@@ -290,6 +570,31 @@ impl GammaRampController {
     pub fn reset_monitor(&mut self, _monitor_device: &str) -> Result<(), String> {
         Err("屏幕滤镜仅支持 Windows 平台".to_string())
     }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn estimate_current_temperature(&self, _monitor_device: &str) -> Result<u32, String> {
+        Err("屏幕滤镜仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn apply_filter_smooth(&mut self, _config: &FilterConfig, _duration_ms: u64) -> Result<(), String> {
+        Err("屏幕滤镜仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn reset_smooth(&mut self, _duration_ms: u64) -> Result<(), String> {
+        Err("屏幕滤镜仅支持 Windows 平台".to_string())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    pub fn apply_to_monitors_smooth(
+        &mut self,
+        _config: &FilterConfig,
+        _monitor_devices: &[String],
+        _duration_ms: u64,
+    ) -> Result<(), String> {
+        Err("屏幕滤镜仅支持 Windows 平台".to_string())
+    }
 }
 
 impl Drop for GammaRampController {
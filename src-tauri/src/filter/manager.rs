@@ -1,7 +1,17 @@
-use crate::filter::{GammaRampController, FilterConfig, FilterPreset, PresetCollection};
-use std::sync::{Arc, Mutex};
+use crate::filter::ddc;
+use crate::filter::preset_store::{self, PresetFormat};
+use crate::filter::{FilterError, GammaRampController, FilterConfig, FilterPreset, HardwareMonitorController, MonitorProfile, MonitorProfileTarget, PresetCollection, Schedule};
+use chrono::{Datelike, Timelike};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
 use std::fs;
 use std::path::PathBuf;
+use tauri::Emitter;
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut};
 
 /// 滤镜管理器
 pub struct FilterManager {
@@ -13,74 +23,305 @@ pub struct FilterManager {
 
     /// 配置文件路径
     config_path: PathBuf,
+
+    /// 预设文件的存储格式，由 `config_path` 的扩展名决定（`.toml` 为 TOML，否则为 JSON）
+    config_format: PresetFormat,
+
+    /// 定时表（日间/夜间自动切换等）
+    schedule: Arc<Mutex<Schedule>>,
+
+    /// 定时表文件路径
+    schedule_path: PathBuf,
+
+    /// 用户正在实时预览（拖动滑杆等）时置为 true，定时调度在此期间不会抢着切换预设
+    preview_active: Arc<AtomicBool>,
+
+    /// DDC/CI 硬件亮度/对比度控制器
+    hardware: HardwareMonitorController,
+
+    /// 本进程正在写入预设文件时置为 true，预设文件监听线程据此跳过由自己触发的重载，
+    /// 避免 create/update/delete_preset 的每次持久化都当成"外部修改"重新处理一遍
+    self_write: Arc<AtomicBool>,
+
+    /// 应用句柄，用于注册/注销全局快捷键
+    app_handle: tauri::AppHandle,
+
+    /// 最近一次 `apply_preset`/`apply_config` 生效的显示器集合；快捷键触发时
+    /// 复用这份缓存，而不必每次按键都重新枚举显示器
+    last_applied_monitors: Arc<Mutex<Vec<String>>>,
+}
+
+/// 系统快捷键触发后的处理结果
+pub enum HotkeyOutcome {
+    /// 应用了某个预设，附带预设 ID
+    AppliedPreset(String),
+    /// 触发了重置快捷键
+    Reset,
+    /// 没有任何快捷键与本次按键匹配
+    NoMatch,
 }
 
 impl FilterManager {
     /// 创建新的滤镜管理器
-    pub fn new(config_dir: PathBuf) -> Result<Self, String> {
+    ///
+    /// `app_handle` 用于在检测到预设文件被外部修改时重新注册快捷键、
+    /// 并向前端发出 `presets-reloaded` 事件
+    pub fn new(config_dir: PathBuf, app_handle: tauri::AppHandle) -> Result<Self, FilterError> {
         // 确保配置目录存在
         fs::create_dir_all(&config_dir)
-            .map_err(|e| format!("无法创建配置目录: {}", e))?;
+            .map_err(|e| FilterError::ConfigDirCreate(e.to_string()))?;
 
-        let config_path = config_dir.join("filter_presets.json");
+        // 优先使用已存在的 TOML 预设文件（用户手改过、希望保留注释的场景），
+        // 否则沿用历史默认的 JSON 路径
+        let toml_path = config_dir.join("filter_presets.toml");
+        let config_path = if toml_path.exists() {
+            toml_path
+        } else {
+            config_dir.join("filter_presets.json")
+        };
+        let config_format = PresetFormat::from_path(&config_path);
 
         // 加载或创建预设集合
         let presets = if config_path.exists() {
-            Self::load_presets(&config_path)?
+            Self::load_presets(&config_path, config_format)?
         } else {
             let default_presets = PresetCollection::default();
-            Self::save_presets(&config_path, &default_presets)?;
+            Self::save_presets(&config_path, &default_presets, config_format)?;
             default_presets
         };
 
-        Ok(Self {
-            controller: Arc::new(Mutex::new(GammaRampController::new())),
+        let schedule_path = config_dir.join("filter_schedule.json");
+
+        // 加载或创建定时表
+        let schedule = if schedule_path.exists() {
+            Self::load_schedule(&schedule_path)?
+        } else {
+            let default_schedule = Schedule::default();
+            Self::save_schedule(&schedule_path, &default_schedule)?;
+            default_schedule
+        };
+
+        let manager = Self {
+            controller: Arc::new(Mutex::new(GammaRampController::new(&config_dir))),
             presets: Arc::new(Mutex::new(presets)),
             config_path,
-        })
+            config_format,
+            schedule: Arc::new(Mutex::new(schedule)),
+            schedule_path,
+            preview_active: Arc::new(AtomicBool::new(false)),
+            hardware: HardwareMonitorController::new(),
+            self_write: Arc::new(AtomicBool::new(false)),
+            app_handle: app_handle.clone(),
+            last_applied_monitors: Arc::new(Mutex::new(Vec::new())),
+        };
+
+        manager.start_preset_watcher(app_handle);
+
+        Ok(manager)
+    }
+
+    /// 启动预设文件监听线程
+    ///
+    /// 使用 `notify` 监听 `config_path`；编辑器保存往往会在极短时间内触发多次
+    /// 修改事件，这里合并 300ms 内的连续事件为一次重载。重载时先校验每个
+    /// `FilterConfig`，格式错误或参数越界的文件会被直接忽略，不影响当前运行状态
+    fn start_preset_watcher(&self, app_handle: tauri::AppHandle) {
+        let config_path = self.config_path.clone();
+        let config_format = self.config_format;
+        let presets = Arc::clone(&self.presets);
+        let self_write = Arc::clone(&self.self_write);
+
+        let (tx, rx) = mpsc::channel::<()>();
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<Event>| {
+            if let Ok(event) = res {
+                if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                    let _ = tx.send(());
+                }
+            }
+        }) {
+            Ok(w) => w,
+            Err(e) => {
+                eprintln!("警告：无法启动预设文件监听: {}", e);
+                return;
+            }
+        };
+
+        if let Err(e) = watcher.watch(&config_path, RecursiveMode::NonRecursive) {
+            eprintln!("警告：无法监听预设文件 {:?}: {}", config_path, e);
+            return;
+        }
+
+        std::thread::spawn(move || {
+            // 持有 watcher，避免线程启动后它被立即 drop 而停止监听
+            let _watcher = watcher;
+
+            while rx.recv().is_ok() {
+                // 合并短时间内的连续写入事件
+                while rx.recv_timeout(Duration::from_millis(300)).is_ok() {}
+
+                // 这次修改是本进程自己 persist_presets 写入触发的，跳过，避免把内部操作
+                // 误当成外部编辑重新处理一遍（重复校验、重复重载是无害的，但会重复
+                // 重新注册快捷键并多发一次 presets-reloaded 事件）
+                if self_write.load(Ordering::SeqCst) {
+                    continue;
+                }
+
+                let content = match fs::read_to_string(&config_path) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        eprintln!("警告：重新读取预设文件失败，已忽略本次外部修改: {}", e);
+                        continue;
+                    }
+                };
+
+                let parsed = match preset_store::parse_presets(&content, config_format) {
+                    Ok(p) => p,
+                    Err(e) => {
+                        eprintln!("警告：预设文件格式错误，已忽略本次外部修改: {}", e);
+                        continue;
+                    }
+                };
+
+                if let Some(err) = parsed.presets.values().find_map(|p| p.config.validate().err()) {
+                    eprintln!("警告：预设文件中存在无效配置，已忽略本次外部修改: {}", err);
+                    continue;
+                }
+
+                let (snapshot, reset_hotkey) = {
+                    let mut guard = presets.lock().unwrap();
+                    *guard = parsed;
+                    (guard.get_all_presets(), guard.reset_hotkey.clone())
+                };
+
+                reregister_hotkeys(&app_handle, &snapshot, reset_hotkey.as_deref());
+
+                if let Err(e) = app_handle.emit("presets-reloaded", ()) {
+                    eprintln!("警告：发送 presets-reloaded 事件失败: {}", e);
+                }
+            }
+        });
     }
 
     /// 加载预设集合
-    fn load_presets(path: &PathBuf) -> Result<PresetCollection, String> {
+    fn load_presets(path: &PathBuf, format: PresetFormat) -> Result<PresetCollection, FilterError> {
+        preset_store::load_presets(path, format)
+    }
+
+    /// 保存预设集合
+    fn save_presets(
+        path: &PathBuf,
+        presets: &PresetCollection,
+        format: PresetFormat,
+    ) -> Result<(), FilterError> {
+        preset_store::save_presets(path, presets, format)
+    }
+
+    /// 持久化当前预设集合
+    fn persist_presets(&self) -> Result<(), FilterError> {
+        self.self_write.store(true, Ordering::SeqCst);
+
+        let result = {
+            let presets = self.presets.lock().unwrap();
+            Self::save_presets(&self.config_path, &presets, self.config_format)
+        };
+
+        // 延迟清除标记：文件系统通知 + 监听线程的防抖窗口有延迟，标记必须覆盖到
+        // 监听线程真正处理这次事件的那一刻，过早清除会让自己的写入被当成外部修改
+        let self_write = Arc::clone(&self.self_write);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            self_write.store(false, Ordering::SeqCst);
+        });
+
+        result
+    }
+
+    /// 加载定时表
+    fn load_schedule(path: &PathBuf) -> Result<Schedule, FilterError> {
         let content = fs::read_to_string(path)
-            .map_err(|e| format!("无法读取配置文件: {}", e))?;
+            .map_err(|e| FilterError::ConfigRead(e.to_string()))?;
 
         serde_json::from_str(&content)
-            .map_err(|e| format!("配置文件格式错误: {}", e))
+            .map_err(|e| FilterError::ConfigParse(e.to_string()))
     }
 
-    /// 保存预设集合
-    fn save_presets(path: &PathBuf, presets: &PresetCollection) -> Result<(), String> {
-        let content = serde_json::to_string_pretty(presets)
-            .map_err(|e| format!("无法序列化配置: {}", e))?;
+    /// 保存定时表
+    fn save_schedule(path: &PathBuf, schedule: &Schedule) -> Result<(), FilterError> {
+        let content = serde_json::to_string_pretty(schedule)
+            .map_err(|e| FilterError::Serialize(e.to_string()))?;
 
         fs::write(path, content)
-            .map_err(|e| format!("无法写入配置文件: {}", e))
+            .map_err(|e| FilterError::ConfigRead(e.to_string()))
     }
 
-    /// 持久化当前预设集合
-    fn persist_presets(&self) -> Result<(), String> {
-        let presets = self.presets.lock().unwrap();
-        Self::save_presets(&self.config_path, &presets)
+    /// 获取当前定时表
+    pub fn get_schedule(&self) -> Schedule {
+        self.schedule.lock().unwrap().clone()
+    }
+
+    /// 设置（覆盖）定时表
+    pub fn set_schedule(&self, schedule: Schedule) -> Result<(), FilterError> {
+        let mut current = self.schedule.lock().unwrap();
+        *current = schedule;
+        Self::save_schedule(&self.schedule_path, &current)
     }
 
-    /// 获取所有预设
+    /// 标记当前是否处于实时预览（例如拖动滑杆）状态；为 true 时定时调度暂停自动切换
+    pub fn set_preview_active(&self, active: bool) {
+        self.preview_active.store(active, Ordering::SeqCst);
+    }
+
+    /// 按定时表检查当前该生效哪个预设，如与当前激活预设不同则应用并返回新预设 ID
+    ///
+    /// 在用户正于实时预览中拖动参数时（`preview_active`）直接跳过，避免和用户操作打架
+    pub fn tick_schedule(&self) -> Result<Option<String>, FilterError> {
+        if self.preview_active.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
+
+        let schedule = self.get_schedule();
+        if schedule.entries.is_empty() {
+            return Ok(None);
+        }
+
+        let now = chrono::Local::now();
+        let now_minutes = (now.hour() * 60 + now.minute()) as u32;
+
+        let target_preset_id = match schedule.active_preset_at(now_minutes, now.year(), now.month(), now.day()) {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+
+        let currently_active = self.get_active_preset().map(|p| p.id);
+        if currently_active.as_deref() == Some(target_preset_id.as_str()) {
+            return Ok(None);
+        }
+
+        let monitors = crate::filter::enumerate_monitors()
+            .map_err(FilterError::MonitorApplyFailed)?;
+        let devices: Vec<String> = monitors.into_iter().map(|m| m.device_name).collect();
+
+        self.apply_preset(&target_preset_id, devices, None)?;
+
+        Ok(Some(target_preset_id))
+    }
+
+    /// 获取所有预设（内置层 + 用户层）
     pub fn get_all_presets(&self) -> Vec<FilterPreset> {
         let presets = self.presets.lock().unwrap();
-        presets.presets.values().cloned().collect()
+        presets.get_all_presets()
     }
 
     /// 获取预设
-    pub fn get_preset(&self, preset_id: &str) -> Result<FilterPreset, String> {
+    pub fn get_preset(&self, preset_id: &str) -> Result<FilterPreset, FilterError> {
         let presets = self.presets.lock().unwrap();
         presets
             .get_preset(preset_id)
-            .cloned()
-            .ok_or_else(|| "预设不存在".to_string())
+            .ok_or_else(|| FilterError::PresetNotFound(preset_id.to_string()))
     }
 
     /// 创建新预设
-    pub fn create_preset(&self, name: String, config: FilterConfig, hotkey: Option<String>) -> Result<String, String> {
+    pub fn create_preset(&self, name: String, config: FilterConfig, hotkey: Option<String>) -> Result<String, FilterError> {
         let mut presets = self.presets.lock().unwrap();
 
         // 验证配置
@@ -101,24 +342,25 @@ impl FilterManager {
             hotkey,
             config,
             is_default: false,
+            derived_from: None,
         };
 
         presets.upsert_preset(preset);
         drop(presets);
 
         self.persist_presets()?;
+        self.resync_hotkeys();
         Ok(id)
     }
 
     /// 更新预设
-    pub fn update_preset(&self, preset_id: &str, name: Option<String>, config: Option<FilterConfig>, hotkey: Option<Option<String>>) -> Result<(), String> {
+    pub fn update_preset(&self, preset_id: &str, name: Option<String>, config: Option<FilterConfig>, hotkey: Option<Option<String>>) -> Result<(), FilterError> {
         let mut presets = self.presets.lock().unwrap();
 
-        // 获取现有预设
+        // 获取现有预设（若为内置预设，下面的 upsert 会在用户层写入一份同 ID 的覆盖副本）
         let mut preset = presets
             .get_preset(preset_id)
-            .cloned()
-            .ok_or_else(|| "预设不存在".to_string())?;
+            .ok_or_else(|| FilterError::PresetNotFound(preset_id.to_string()))?;
 
         // 更新名称
         if let Some(new_name) = name {
@@ -143,42 +385,259 @@ impl FilterManager {
         drop(presets);
 
         self.persist_presets()?;
+        self.resync_hotkeys();
         Ok(())
     }
 
+    /// 对单个预设中的某个嵌套字段做原地编辑，仅在 TOML 存储时有意义
+    ///
+    /// `dotted_key` 是 `FilterConfig` 内的字段路径（例如 `gamma`），实际写入的
+    /// 完整路径是 `presets.<preset_id>.config.<dotted_key>`；与整份重写的
+    /// `create_preset`/`update_preset` 不同，这里只改动这一个字段，文档里其余
+    /// 内容（注释、顺序、空白）原样保留
+    pub fn set_config_value(
+        &self,
+        preset_id: &str,
+        dotted_key: &str,
+        value: toml_edit::Value,
+    ) -> Result<(), FilterError> {
+        if self.config_format != PresetFormat::Toml {
+            return Err(FilterError::InvalidConfig(
+                "当前预设文件不是 TOML 格式，无法增量编辑".to_string(),
+            ));
+        }
+
+        self.self_write.store(true, Ordering::SeqCst);
+
+        let full_key = format!("presets.{}.config.{}", preset_id, dotted_key);
+        let result = preset_store::set_config_value(&self.config_path, &full_key, value);
+
+        let self_write = Arc::clone(&self.self_write);
+        std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            self_write.store(false, Ordering::SeqCst);
+        });
+
+        result?;
+
+        // 增量编辑绕过了内存中的 `PresetCollection`，重新加载一次使其与磁盘保持一致
+        let reloaded = Self::load_presets(&self.config_path, self.config_format)?;
+        *self.presets.lock().unwrap() = reloaded;
+
+        Ok(())
+    }
+
+    /// 将默认预设克隆为一份可自由编辑的用户预设，返回新预设 ID
+    pub fn detach_preset(&self, preset_id: &str) -> Result<String, FilterError> {
+        let mut presets = self.presets.lock().unwrap();
+        let new_id = presets.detach_preset(preset_id)?;
+        drop(presets);
+
+        self.persist_presets()?;
+        Ok(new_id)
+    }
+
     /// 删除预设
-    pub fn delete_preset(&self, preset_id: &str) -> Result<(), String> {
+    pub fn delete_preset(&self, preset_id: &str) -> Result<(), FilterError> {
         let mut presets = self.presets.lock().unwrap();
         presets.delete_preset(preset_id)?;
         drop(presets);
 
         self.persist_presets()?;
+        self.resync_hotkeys();
         Ok(())
     }
 
     /// 重命名预设
-    pub fn rename_preset(&self, preset_id: &str, new_name: String) -> Result<(), String> {
+    pub fn rename_preset(&self, preset_id: &str, new_name: String) -> Result<(), FilterError> {
         self.update_preset(preset_id, Some(new_name), None, None)
     }
 
+    /// 获取当前的重置快捷键
+    pub fn get_reset_hotkey(&self) -> Option<String> {
+        self.presets.lock().unwrap().reset_hotkey.clone()
+    }
+
+    /// 设置（或清除，传入 `None`）重置快捷键
+    pub fn set_reset_hotkey(&self, hotkey: Option<String>) -> Result<(), FilterError> {
+        let mut presets = self.presets.lock().unwrap();
+        presets.set_reset_hotkey(hotkey)?;
+        drop(presets);
+
+        self.persist_presets()?;
+        self.resync_hotkeys();
+        Ok(())
+    }
+
+    /// 获取所有多显示器配置方案
+    pub fn get_all_profiles(&self) -> Vec<MonitorProfile> {
+        self.presets.lock().unwrap().profiles.values().cloned().collect()
+    }
+
+    /// 创建多显示器配置方案，`assignments` 为显示器设备标识到效果的映射
+    pub fn create_profile(
+        &self,
+        name: String,
+        assignments: HashMap<String, MonitorProfileTarget>,
+    ) -> Result<String, FilterError> {
+        let mut presets = self.presets.lock().unwrap();
+        Self::validate_profile_assignments(&presets, &assignments)?;
+
+        let id = format!("profile_{}", uuid::Uuid::new_v4().to_string());
+        presets.upsert_profile(MonitorProfile {
+            id: id.clone(),
+            name,
+            assignments,
+        });
+        drop(presets);
+
+        self.persist_presets()?;
+        Ok(id)
+    }
+
+    /// 更新多显示器配置方案
+    pub fn update_profile(
+        &self,
+        profile_id: &str,
+        name: Option<String>,
+        assignments: Option<HashMap<String, MonitorProfileTarget>>,
+    ) -> Result<(), FilterError> {
+        let mut presets = self.presets.lock().unwrap();
+        let mut profile = presets
+            .get_profile(profile_id)
+            .cloned()
+            .ok_or_else(|| FilterError::PresetNotFound(profile_id.to_string()))?;
+
+        if let Some(new_name) = name {
+            profile.name = new_name;
+        }
+
+        if let Some(new_assignments) = assignments {
+            Self::validate_profile_assignments(&presets, &new_assignments)?;
+            profile.assignments = new_assignments;
+        }
+
+        presets.upsert_profile(profile);
+        drop(presets);
+
+        self.persist_presets()?;
+        Ok(())
+    }
+
+    /// 删除多显示器配置方案
+    pub fn delete_profile(&self, profile_id: &str) -> Result<(), FilterError> {
+        let mut presets = self.presets.lock().unwrap();
+        presets.delete_profile(profile_id)?;
+        drop(presets);
+
+        self.persist_presets()?;
+        Ok(())
+    }
+
+    /// 校验配置方案里引用的预设 ID 和内联配置都合法
+    fn validate_profile_assignments(
+        presets: &PresetCollection,
+        assignments: &HashMap<String, MonitorProfileTarget>,
+    ) -> Result<(), FilterError> {
+        for target in assignments.values() {
+            match target {
+                MonitorProfileTarget::Preset { preset_id } => {
+                    if presets.get_preset(preset_id).is_none() {
+                        return Err(FilterError::PresetNotFound(preset_id.clone()));
+                    }
+                }
+                MonitorProfileTarget::Inline { config } => {
+                    config.validate()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 应用一个多显示器配置方案：按 `assignments` 里的映射逐个显示器下发各自的效果，
+    /// 而不是像 `apply_preset`/`apply_config` 那样把同一份配置套到所有显示器上；
+    /// 映射里没有出现的显示器保持现状不受影响
+    pub fn apply_profile(&self, profile_id: &str, transition_ms: Option<u64>) -> Result<(), FilterError> {
+        let presets = self.presets.lock().unwrap();
+        let profile = presets
+            .get_profile(profile_id)
+            .cloned()
+            .ok_or_else(|| FilterError::PresetNotFound(profile_id.to_string()))?;
+
+        let resolved: Vec<(String, FilterConfig)> = profile
+            .assignments
+            .iter()
+            .map(|(device, target)| {
+                let config = match target {
+                    MonitorProfileTarget::Preset { preset_id } => presets
+                        .get_preset(preset_id)
+                        .map(|p| p.config)
+                        .ok_or_else(|| FilterError::PresetNotFound(preset_id.clone())),
+                    MonitorProfileTarget::Inline { config } => Ok(config.clone()),
+                };
+                config.map(|c| (device.clone(), c))
+            })
+            .collect::<Result<Vec<_>, FilterError>>()?;
+        drop(presets);
+
+        let mut controller = self.controller.lock().unwrap();
+        for (device, config) in &resolved {
+            let effective_config = self.apply_hardware_brightness(config, std::slice::from_ref(device));
+            match transition_ms {
+                Some(duration_ms) => controller
+                    .apply_to_monitors_smooth(&effective_config, std::slice::from_ref(device), duration_ms)
+                    .map_err(FilterError::MonitorApplyFailed)?,
+                None => controller
+                    .apply_filter_to_monitor(&effective_config, device)
+                    .map_err(FilterError::MonitorApplyFailed)?,
+            }
+        }
+        drop(controller);
+
+        *self.last_applied_monitors.lock().unwrap() =
+            resolved.into_iter().map(|(device, _)| device).collect();
+
+        Ok(())
+    }
+
     /// 应用预设
-    pub fn apply_preset(&self, preset_id: &str, monitor_devices: Vec<String>) -> Result<(), String> {
+    ///
+    /// `transition_ms` 为 `Some` 时，会在该毫秒数内从当前显示状态平滑过渡到预设效果，
+    /// 而不是瞬间切换；新的调用会自动取代仍在进行中的过渡（见 `GammaRampController::run_transition`）
+    pub fn apply_preset(
+        &self,
+        preset_id: &str,
+        monitor_devices: Vec<String>,
+        transition_ms: Option<u64>,
+    ) -> Result<(), FilterError> {
         let mut presets = self.presets.lock().unwrap();
 
         // 获取预设
         let preset = presets
             .get_preset(preset_id)
-            .ok_or_else(|| "预设不存在".to_string())?;
+            .ok_or_else(|| FilterError::PresetNotFound(preset_id.to_string()))?;
 
-        // 应用滤镜
-        let mut controller = self.controller.lock().unwrap();
         if monitor_devices.is_empty() {
-            return Err("未选择任何显示器".to_string());
+            return Err(FilterError::NoMonitorSelected);
         }
-        
-        // 应用到指定的显示器列表
-        for device in monitor_devices {
-            controller.apply_filter_to_monitor(&preset.config, &device)?;
+
+        let effective_config = self.apply_hardware_brightness(&preset.config, &monitor_devices);
+
+        // 应用滤镜
+        let mut controller = self.controller.lock().unwrap();
+        match transition_ms {
+            Some(duration_ms) => {
+                controller
+                    .apply_to_monitors_smooth(&effective_config, &monitor_devices, duration_ms)
+                    .map_err(FilterError::MonitorApplyFailed)?;
+            }
+            None => {
+                for device in &monitor_devices {
+                    controller
+                        .apply_filter_to_monitor(&effective_config, device)
+                        .map_err(FilterError::MonitorApplyFailed)?;
+                }
+            }
         }
 
         // 设置为激活预设
@@ -186,36 +645,114 @@ impl FilterManager {
         drop(presets);
 
         self.persist_presets()?;
+        *self.last_applied_monitors.lock().unwrap() = monitor_devices;
         Ok(())
     }
 
     /// 直接应用滤镜配置（用于实时预览，不保存为激活预设）
-    pub fn apply_config(&self, config: &FilterConfig, monitor_devices: Vec<String>) -> Result<(), String> {
+    pub fn apply_config(
+        &self,
+        config: &FilterConfig,
+        monitor_devices: Vec<String>,
+        transition_ms: Option<u64>,
+    ) -> Result<(), FilterError> {
         // 验证配置
         config.validate()?;
 
+        if monitor_devices.is_empty() {
+            return Err(FilterError::NoMonitorSelected);
+        }
+
+        let effective_config = self.apply_hardware_brightness(config, &monitor_devices);
+
         // 应用滤镜
         let mut controller = self.controller.lock().unwrap();
-        if monitor_devices.is_empty() {
-            return Err("未选择任何显示器".to_string());
+        match transition_ms {
+            Some(duration_ms) => {
+                controller
+                    .apply_to_monitors_smooth(&effective_config, &monitor_devices, duration_ms)
+                    .map_err(FilterError::MonitorApplyFailed)?;
+            }
+            None => {
+                for device in &monitor_devices {
+                    controller
+                        .apply_filter_to_monitor(&effective_config, device)
+                        .map_err(FilterError::MonitorApplyFailed)?;
+                }
+            }
         }
-        
+        drop(controller);
+
+        *self.last_applied_monitors.lock().unwrap() = monitor_devices;
+        Ok(())
+    }
+
+    /// 若配置启用了硬件亮度（`use_hardware_brightness` + `hardware_brightness_percent`），
+    /// 对支持 DDC/CI 的显示器下发硬件亮度指令，并返回一份 `brightness` 归零的配置副本，
+    /// 避免 Gamma Ramp 与硬件背光同时调整亮度造成叠加；不支持 DDC/CI 或未启用时原样返回
+    fn apply_hardware_brightness(&self, config: &FilterConfig, monitor_devices: &[String]) -> FilterConfig {
+        if !config.use_hardware_brightness {
+            return config.clone();
+        }
+
+        let percent = match config.hardware_brightness_percent {
+            Some(p) => p as u32,
+            None => return config.clone(),
+        };
+
+        let mut any_applied = false;
         for device in monitor_devices {
-            controller.apply_filter_to_monitor(config, &device)?;
+            if !ddc::supports_ddc(device) {
+                continue;
+            }
+
+            match self.hardware.set_brightness_for_device(device, percent) {
+                Ok(_) => any_applied = true,
+                Err(e) => eprintln!("警告：显示器 {} 设置硬件亮度失败，将回退到 Gamma Ramp: {}", device, e),
+            }
         }
-        Ok(())
+
+        if any_applied {
+            FilterConfig {
+                brightness: 0.0,
+                ..config.clone()
+            }
+        } else {
+            config.clone()
+        }
+    }
+
+    /// 查询指定显示器的 DDC/CI 能力与当前硬件亮度/对比度
+    pub fn get_ddc_capabilities(&self, monitor_device: &str) -> ddc::DdcCapabilities {
+        ddc::get_ddc_capabilities(monitor_device)
+    }
+
+    /// 直接对某个显示器下发硬件亮度指令（VCP 0x10），不经由预设/配置，供前端做
+    /// 独立的硬件亮度调节；显示器不支持 DDC/CI 或设置失败时返回
+    /// `FilterError::MonitorApplyFailed`
+    pub fn set_hardware_brightness(&self, monitor_device: &str, percent: u8) -> Result<(), FilterError> {
+        if percent > 100 {
+            return Err(FilterError::InvalidConfig(format!(
+                "硬件亮度百分比必须在 0 到 100 之间，当前值: {}",
+                percent
+            )));
+        }
+
+        self.hardware
+            .set_brightness_for_device(monitor_device, percent as u32)
+            .map_err(FilterError::MonitorApplyFailed)
     }
 
     /// 获取当前激活的预设
     pub fn get_active_preset(&self) -> Option<FilterPreset> {
         let presets = self.presets.lock().unwrap();
-        presets.get_active_preset().cloned()
+        presets.get_active_preset()
     }
 
     /// 重置滤镜（恢复到系统默认）
-    pub fn reset_filter(&self) -> Result<(), String> {
+    pub fn reset_filter(&self) -> Result<(), FilterError> {
         let mut controller = self.controller.lock().unwrap();
-        controller.reset()?;
+        controller.reset().map_err(FilterError::MonitorApplyFailed)?;
 
         let mut presets = self.presets.lock().unwrap();
         presets.active_preset_id = None;
@@ -226,35 +763,192 @@ impl FilterManager {
     }
 
     /// 导出预设集合到 JSON 字符串
-    pub fn export_presets(&self) -> Result<String, String> {
+    pub fn export_presets(&self) -> Result<String, FilterError> {
         let presets = self.presets.lock().unwrap();
         serde_json::to_string_pretty(&*presets)
-            .map_err(|e| format!("导出失败: {}", e))
+            .map_err(|e| FilterError::Serialize(e.to_string()))
     }
 
     /// 从 JSON 字符串导入预设集合
-    pub fn import_presets(&self, json: &str) -> Result<(), String> {
+    ///
+    /// 导入只合并进用户层：与现有预设（含内置层）ID 冲突的项会被重新分配 ID 并标注来源，
+    /// 不会覆盖当前已有的预设；重置快捷键、激活预设、配置方案均保持不变
+    pub fn import_presets(&self, json: &str) -> Result<(), FilterError> {
         let imported: PresetCollection = serde_json::from_str(json)
-            .map_err(|e| format!("导入失败: {}", e))?;
+            .map_err(|e| FilterError::ConfigParse(e.to_string()))?;
 
         let mut presets = self.presets.lock().unwrap();
-        *presets = imported;
+        presets.merge_presets(imported.presets.into_values().collect());
         drop(presets);
 
         self.persist_presets()?;
+        self.resync_hotkeys();
         Ok(())
     }
 
-    /// 重置为默认预设
-    pub fn reset_to_defaults(&self) -> Result<(), String> {
-        let default_presets = PresetCollection::default();
+    /// 重置为默认预设：只清空用户层（自定义预设、重置快捷键、配置方案），
+    /// 内置预设是编译期常量，始终保留，不受影响
+    pub fn reset_to_defaults(&self) -> Result<(), FilterError> {
         let mut presets = self.presets.lock().unwrap();
-        *presets = default_presets;
+        presets.presets.clear();
+        presets.profiles.clear();
+        presets.reset_hotkey = None;
+        presets.active_preset_id = Some("default".to_string());
         drop(presets);
 
         self.persist_presets()?;
+        self.resync_hotkeys();
         Ok(())
     }
+
+    /// 启动全局快捷键：把所有预设的 `hotkey` 以及重置快捷键注册为系统级全局快捷键
+    ///
+    /// 按键后的实际分发发生在 `tauri_plugin_global_shortcut` 的全局 handler 中，
+    /// 经 `trigger_hotkey` 回调到这里；单个快捷键解析/注册失败（多半是已被其他程序
+    /// 占用）不会中断其余快捷键的注册——逐个尝试、失败的记下来继续下一个，最后如果
+    /// 有任何失败，汇总成一个 `FilterError::HotkeyConflict` 报出，调用方据此知道
+    /// "并非所有快捷键都生效"，而不是因为一个冲突就让全部快捷键都注册不上
+    pub fn start_hotkeys(&self) -> Result<(), FilterError> {
+        self.app_handle
+            .global_shortcut()
+            .unregister_all()
+            .map_err(|e| FilterError::HotkeyConflict(format!("取消注册快捷键失败: {}", e)))?;
+
+        let mut failures = Vec::new();
+
+        let presets = self.get_all_presets();
+        for preset in &presets {
+            if let Some(ref hotkey) = preset.hotkey {
+                match Shortcut::from_str(hotkey) {
+                    Ok(shortcut) => {
+                        if let Err(e) = self.app_handle.global_shortcut().register(shortcut) {
+                            failures.push(format!(
+                                "快捷键 {} 注册失败，可能已被其他程序占用: {}",
+                                hotkey, e
+                            ));
+                        }
+                    }
+                    Err(e) => failures.push(format!("快捷键 {} 解析失败: {}", hotkey, e)),
+                }
+            }
+        }
+
+        let reset_hotkey = self.presets.lock().unwrap().reset_hotkey.clone();
+        if let Some(ref hotkey) = reset_hotkey {
+            match Shortcut::from_str(hotkey) {
+                Ok(shortcut) => {
+                    if let Err(e) = self.app_handle.global_shortcut().register(shortcut) {
+                        failures.push(format!(
+                            "重置快捷键 {} 注册失败，可能已被其他程序占用: {}",
+                            hotkey, e
+                        ));
+                    }
+                }
+                Err(e) => failures.push(format!("重置快捷键 {} 解析失败: {}", hotkey, e)),
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(FilterError::HotkeyConflict(failures.join("; ")))
+        }
+    }
+
+    /// 停止全局快捷键：取消注册当前进程持有的所有全局快捷键
+    pub fn stop_hotkeys(&self) -> Result<(), FilterError> {
+        self.app_handle
+            .global_shortcut()
+            .unregister_all()
+            .map_err(|e| FilterError::HotkeyConflict(format!("取消注册快捷键失败: {}", e)))
+    }
+
+    /// 创建/更新/删除预设、导入预设后尽力而为地重新同步快捷键注册；与 `start_hotkeys`
+    /// 不同，这里只记警告、不让快捷键注册失败影响预设本身的增删改是否成功
+    fn resync_hotkeys(&self) {
+        if let Err(e) = self.start_hotkeys() {
+            eprintln!("警告：重新同步快捷键注册失败: {}", e);
+        }
+    }
+
+    /// 处理一次全局快捷键按下事件：优先匹配重置快捷键，否则在所有预设里查找匹配的
+    /// `hotkey` 并应用它——应用时复用 `last_applied_monitors` 缓存的显示器集合，
+    /// 缓存为空（例如启动后还从未手动应用过）时才现场枚举一次显示器
+    pub fn trigger_hotkey(&self, shortcut: &Shortcut) -> Result<HotkeyOutcome, FilterError> {
+        let pressed = format!("{:?}", shortcut);
+
+        let reset_hotkey = self.presets.lock().unwrap().reset_hotkey.clone();
+        if let Some(ref hotkey) = reset_hotkey {
+            if let Ok(reset_shortcut) = Shortcut::from_str(hotkey) {
+                if format!("{:?}", reset_shortcut) == pressed {
+                    self.reset_filter()?;
+                    return Ok(HotkeyOutcome::Reset);
+                }
+            }
+        }
+
+        let matched = self.get_all_presets().into_iter().find(|preset| {
+            preset
+                .hotkey
+                .as_deref()
+                .and_then(|key| Shortcut::from_str(key).ok())
+                .map(|shortcut| format!("{:?}", shortcut) == pressed)
+                .unwrap_or(false)
+        });
+
+        let preset = match matched {
+            Some(preset) => preset,
+            None => return Ok(HotkeyOutcome::NoMatch),
+        };
+
+        let cached = self.last_applied_monitors.lock().unwrap().clone();
+        let devices = if cached.is_empty() {
+            crate::filter::enumerate_monitors()
+                .map_err(FilterError::MonitorApplyFailed)?
+                .into_iter()
+                .map(|m| m.device_name)
+                .collect()
+        } else {
+            cached
+        };
+
+        self.apply_preset(&preset.id, devices, None)?;
+        Ok(HotkeyOutcome::AppliedPreset(preset.id))
+    }
+}
+
+/// 按照预设当前的快捷键（以及重置快捷键）重新注册全局快捷键；与
+/// `FilterManager::start_hotkeys` 的逻辑一致，但这里是尽力而为、只记警告不报错的版本，
+/// 供预设文件监听线程使用，不会因为某个快捷键被占用就中断重载；CRUD 操作后的重新同步
+/// 走的是 `FilterManager::resync_hotkeys`，它调用 `start_hotkeys` 并同样只记警告
+fn reregister_hotkeys(app: &tauri::AppHandle, presets: &[FilterPreset], reset_hotkey: Option<&str>) {
+    if let Err(e) = app.global_shortcut().unregister_all() {
+        eprintln!("警告：取消注册快捷键失败: {}", e);
+    }
+
+    for preset in presets {
+        if let Some(ref hotkey) = preset.hotkey {
+            match Shortcut::from_str(hotkey) {
+                Ok(shortcut) => {
+                    if let Err(e) = app.global_shortcut().register(shortcut) {
+                        eprintln!("警告：注册快捷键失败 {} ({}): {}", hotkey, preset.name, e);
+                    }
+                }
+                Err(e) => eprintln!("警告：解析快捷键失败 {} ({}): {}", hotkey, preset.name, e),
+            }
+        }
+    }
+
+    if let Some(hotkey) = reset_hotkey {
+        match Shortcut::from_str(hotkey) {
+            Ok(shortcut) => {
+                if let Err(e) = app.global_shortcut().register(shortcut) {
+                    eprintln!("警告：注册重置快捷键失败 {}: {}", hotkey, e);
+                }
+            }
+            Err(e) => eprintln!("警告：解析重置快捷键失败 {}: {}", hotkey, e),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -262,10 +956,14 @@ mod tests {
     use super::*;
     use std::env;
 
+    fn test_app_handle() -> tauri::AppHandle {
+        tauri::test::mock_app().handle().clone()
+    }
+
     #[test]
     fn test_filter_manager_creation() {
         let temp_dir = env::temp_dir().join("t2_test_filter");
-        let manager = FilterManager::new(temp_dir.clone());
+        let manager = FilterManager::new(temp_dir.clone(), test_app_handle());
         assert!(manager.is_ok());
 
         // 清理
@@ -275,7 +973,7 @@ mod tests {
     #[test]
     fn test_create_and_delete_preset() {
         let temp_dir = env::temp_dir().join("t2_test_filter_2");
-        let manager = FilterManager::new(temp_dir.clone()).unwrap();
+        let manager = FilterManager::new(temp_dir.clone(), test_app_handle()).unwrap();
 
         // 创建预设
         let config = FilterConfig::default();
@@ -1,10 +1,20 @@
 // 屏幕滤镜模块
+pub mod baseline_store;
+pub mod ddc;
+pub mod error;
 pub mod gamma_ramp;
 pub mod types;
 pub mod manager;
 pub mod monitor;
+pub mod preset_store;
+pub mod preview;
+pub mod schedule;
+pub mod solar;
 
+pub use ddc::{get_ddc_capabilities, DdcCapabilities, HardwareMonitorController};
+pub use error::FilterError;
 pub use gamma_ramp::GammaRampController;
-pub use types::{FilterConfig, FilterPreset, PresetCollection};
+pub use schedule::{GeoLocation, Schedule, ScheduleEntry};
+pub use types::{FilterConfig, FilterPreset, MonitorProfile, MonitorProfileTarget, PresetCollection};
 pub use manager::FilterManager;
 pub use monitor::{MonitorInfo, enumerate_monitors};
@@ -1,3 +1,4 @@
+use crate::filter::ddc;
 use serde::{Deserialize, Serialize};
 
 /// 显示器信息
@@ -7,6 +8,21 @@ pub struct MonitorInfo {
     pub name: String,
     pub device_name: String,
     pub is_primary: bool,
+
+    /// 分辨率宽度（像素），无法获取时为 None
+    pub width: Option<u32>,
+
+    /// 分辨率高度（像素），无法获取时为 None
+    pub height: Option<u32>,
+
+    /// 显示器在虚拟桌面中的位置 (x, y)，无法获取时为 None
+    pub position: Option<(i32, i32)>,
+
+    /// 刷新率 (Hz)，无法获取时为 None
+    pub refresh_hz: Option<u32>,
+
+    /// 是否支持 DDC/CI 硬件亮度/对比度控制（见 `ddc::supports_ddc`）
+    pub supports_ddc: bool,
 }
 
 #[cfg(target_os = "windows")]
@@ -14,7 +30,9 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
     use windows::Win32::Graphics::Gdi::{
         EnumDisplayDevicesW, DISPLAY_DEVICEW, DISPLAY_DEVICE_ACTIVE,
         DISPLAY_DEVICE_ATTACHED_TO_DESKTOP, DISPLAY_DEVICE_PRIMARY_DEVICE,
+        DISPLAY_DEVICE_MIRRORING_DRIVER,
     };
+    use windows::Win32::UI::WindowsAndMessaging::{GetSystemMetrics, SM_CMONITORS};
     use windows::core::PCWSTR;
 
     let mut monitors = Vec::new();
@@ -38,6 +56,16 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
             break;
         }
 
+        // 跳过镜像驱动适配器（例如远程桌面/录屏软件注册的虚拟适配器），
+        // 它们不对应真实物理显示器，对其 CreateDCW/SetDeviceGammaRamp 可能悄悄失败或无效
+        if adapter_device.StateFlags & DISPLAY_DEVICE_MIRRORING_DRIVER != 0 {
+            adapter_index += 1;
+            if adapter_index > 8 {
+                break;
+            }
+            continue;
+        }
+
         let adapter_name = unsafe {
             let len = adapter_device.DeviceName.iter()
                 .position(|&c| c == 0)
@@ -66,10 +94,11 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
                 break;
             }
 
-            // 只处理连接到桌面的激活显示器
+            // 只处理连接到桌面的激活显示器，并跳过镜像驱动/伪显示器
             if (monitor_device.StateFlags & DISPLAY_DEVICE_ATTACHED_TO_DESKTOP != 0) &&
-               (monitor_device.StateFlags & DISPLAY_DEVICE_ACTIVE != 0) {
-                
+               (monitor_device.StateFlags & DISPLAY_DEVICE_ACTIVE != 0) &&
+               (monitor_device.StateFlags & DISPLAY_DEVICE_MIRRORING_DRIVER == 0) {
+
                 let monitor_string = unsafe {
                     let len = monitor_device.DeviceString.iter()
                         .position(|&c| c == 0)
@@ -94,11 +123,18 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
                     }
                 };
 
+                let geometry = get_monitor_geometry(&adapter_name);
+
                 monitors.push(MonitorInfo {
                     index: monitors.len(),
                     name: display_name,
                     device_name: adapter_name.clone(),
                     is_primary,
+                    width: geometry.map(|g| g.0),
+                    height: geometry.map(|g| g.1),
+                    position: geometry.map(|g| g.2),
+                    refresh_hz: geometry.map(|g| g.3),
+                    supports_ddc: ddc::supports_ddc(&adapter_name),
                 });
             }
 
@@ -114,6 +150,16 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
         }
     }
 
+    // 交叉核对系统报告的物理显示器数量，辅助发现过滤逻辑遗漏或多算的情况
+    let system_monitor_count = unsafe { GetSystemMetrics(SM_CMONITORS) };
+    if system_monitor_count > 0 && monitors.len() != system_monitor_count as usize {
+        eprintln!(
+            "警告：枚举到 {} 个显示器，但系统报告 SM_CMONITORS = {}",
+            monitors.len(),
+            system_monitor_count
+        );
+    }
+
     if monitors.is_empty() {
         // 如果没有找到显示器，返回默认的主显示器
         monitors.push(MonitorInfo {
@@ -121,12 +167,53 @@ pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
             name: "主显示器".to_string(),
             device_name: "\\\\.\\DISPLAY1".to_string(),
             is_primary: true,
+            width: None,
+            height: None,
+            position: None,
+            refresh_hz: None,
+            supports_ddc: false,
         });
     }
 
     Ok(monitors)
 }
 
+/// 获取适配器当前的分辨率、位置与刷新率
+///
+/// 通过 `EnumDisplaySettingsExW` + `ENUM_CURRENT_SETTINGS` 读取 `DEVMODEW`，
+/// 失败时返回 `None`，调用方应保留该显示器但把几何信息置空，而不是丢弃它
+#[cfg(target_os = "windows")]
+fn get_monitor_geometry(adapter_name: &str) -> Option<(u32, u32, (i32, i32), u32)> {
+    use windows::Win32::Graphics::Gdi::{EnumDisplaySettingsExW, DEVMODEW, ENUM_CURRENT_SETTINGS};
+
+    let adapter_name_wide: Vec<u16> = adapter_name.encode_utf16().chain(std::iter::once(0)).collect();
+
+    let mut dev_mode: DEVMODEW = unsafe { std::mem::zeroed() };
+    dev_mode.dmSize = std::mem::size_of::<DEVMODEW>() as u16;
+
+    let success = unsafe {
+        EnumDisplaySettingsExW(
+            PCWSTR(adapter_name_wide.as_ptr()),
+            ENUM_CURRENT_SETTINGS,
+            &mut dev_mode,
+            windows::Win32::Graphics::Gdi::ENUM_DISPLAY_SETTINGS_EX_FLAGS(0),
+        )
+    };
+
+    if !success.as_bool() {
+        return None;
+    }
+
+    let position = unsafe { dev_mode.Anonymous1.Anonymous2.dmPosition };
+
+    Some((
+        dev_mode.dmPelsWidth,
+        dev_mode.dmPelsHeight,
+        (position.x, position.y),
+        dev_mode.dmDisplayFrequency,
+    ))
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn enumerate_monitors() -> Result<Vec<MonitorInfo>, String> {
     Err("显示器枚举仅支持 Windows 平台".to_string())
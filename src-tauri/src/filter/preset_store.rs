@@ -0,0 +1,283 @@
+// 预设文件的存储后端：JSON（默认）或 TOML
+//
+// TOML 后端基于 toml_edit 的 `DocumentMut`：加载走 serde 整份反序列化，保存
+// （`save_presets_toml`）按条目合并进已有文档，`set_config_value` 则是更细粒度的
+// 单字段原地编辑——三者共同的目标是尽量保留文档里原有的注释、键顺序和空白，
+// 不会因为改了一个预设就把整份文件重新序列化抹掉
+
+use crate::filter::error::FilterError;
+use crate::filter::types::PresetCollection;
+use std::path::Path;
+
+/// 预设集合的存储格式，由文件扩展名决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetFormat {
+    Json,
+    Toml,
+}
+
+impl PresetFormat {
+    /// 根据扩展名判断格式；无法识别的扩展名按 JSON 处理，与历史行为保持一致
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => PresetFormat::Toml,
+            _ => PresetFormat::Json,
+        }
+    }
+}
+
+/// 读取预设集合
+pub fn load_presets(path: &Path, format: PresetFormat) -> Result<PresetCollection, FilterError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| FilterError::ConfigRead(e.to_string()))?;
+
+    parse_presets(&content, format)
+}
+
+/// 从已读取的文件内容中解析预设集合（供监听线程复用，避免重复读盘）
+pub fn parse_presets(content: &str, format: PresetFormat) -> Result<PresetCollection, FilterError> {
+    match format {
+        PresetFormat::Json => {
+            serde_json::from_str(content).map_err(|e| FilterError::ConfigParse(e.to_string()))
+        }
+        PresetFormat::Toml => {
+            toml_edit::de::from_str(content).map_err(|e| FilterError::ConfigParse(e.to_string()))
+        }
+    }
+}
+
+/// 写出预设集合（用于创建/更新/删除预设这类改变整体结构的操作）
+///
+/// JSON 没有注释，整份重新序列化写出即可；TOML 则委托给 `save_presets_toml`，
+/// 对已有文档做条目级合并，避免把用户手写的注释、顺序全部抹掉
+pub fn save_presets(
+    path: &Path,
+    presets: &PresetCollection,
+    format: PresetFormat,
+) -> Result<(), FilterError> {
+    match format {
+        PresetFormat::Json => {
+            let content = serde_json::to_string_pretty(presets)
+                .map_err(|e| FilterError::Serialize(e.to_string()))?;
+            std::fs::write(path, content).map_err(|e| FilterError::ConfigRead(e.to_string()))
+        }
+        PresetFormat::Toml => save_presets_toml(path, presets),
+    }
+}
+
+/// 把 `presets` 合并写回 TOML 文档，而不是整份重新序列化：
+/// - `presets`/`profiles` 这两张表按条目（预设/配置方案 ID）逐个比较，值未变的条目
+///   原样保留在文档里（连同其注释、顺序），只有新增/变化/删除的条目才会被整体替换或移除
+/// - `active_preset_id`/`reset_hotkey` 这类顶层标量字段，值不变时不做任何写入
+///
+/// 旧文件不存在或无法解析时，退化为从空文档开始（等价于整份重写）
+fn save_presets_toml(path: &Path, presets: &PresetCollection) -> Result<(), FilterError> {
+    let mut doc = std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| content.parse::<toml_edit::DocumentMut>().ok())
+        .unwrap_or_default();
+
+    let mut desired = toml_edit::ser::to_document(presets)
+        .map_err(|e| FilterError::Serialize(e.to_string()))?;
+
+    // `to_document` 只把根节点转为标准表，嵌套结构仍是内联表；把 `presets`/`profiles`
+    // 及其下每个条目展开为标准表，条目才会以 `[presets.<id>]` 这样的表头参与下面的合并
+    for key in ["presets", "profiles"] {
+        if let Some(item) = desired.get_mut(key) {
+            let taken = std::mem::take(item);
+            *item = taken.into_table().map(toml_edit::Item::Table).unwrap_or_else(|i| i);
+            if let Some(table) = item.as_table_mut() {
+                for (_, entry) in table.iter_mut() {
+                    let taken = std::mem::take(entry);
+                    *entry = taken.into_table().map(toml_edit::Item::Table).unwrap_or_else(|i| i);
+                }
+            }
+        }
+    }
+
+    merge_sub_table(doc.as_table_mut(), desired.as_table(), "presets");
+    merge_sub_table(doc.as_table_mut(), desired.as_table(), "profiles");
+
+    for key in ["active_preset_id", "reset_hotkey"] {
+        match desired.get(key) {
+            Some(value) => {
+                let unchanged = doc
+                    .get(key)
+                    .is_some_and(|existing| existing.to_string() == value.to_string());
+                if !unchanged {
+                    doc[key] = value.clone();
+                }
+            }
+            None => {
+                doc.as_table_mut().remove(key);
+            }
+        }
+    }
+
+    std::fs::write(path, doc.to_string()).map_err(|e| FilterError::ConfigRead(e.to_string()))
+}
+
+/// 把 `desired` 里的二级表 `table_key`（`presets` 或 `profiles`）合并进 `doc`：
+/// 逐条目比较序列化结果，未变的条目保留原始格式，变化/新增的条目整体替换，
+/// `doc` 里多出来的（已被删除的）条目移除
+fn merge_sub_table(doc: &mut toml_edit::Table, desired: &toml_edit::Table, table_key: &str) {
+    let desired_sub = match desired.get(table_key).and_then(|item| item.as_table()) {
+        Some(t) => t,
+        None => {
+            doc.remove(table_key);
+            return;
+        }
+    };
+
+    if doc.get(table_key).and_then(|item| item.as_table_like()).is_none() {
+        let mut table = toml_edit::Table::new();
+        table.set_implicit(true);
+        doc.insert(table_key, toml_edit::Item::Table(table));
+    }
+
+    let target = doc
+        .get_mut(table_key)
+        .and_then(|item| item.as_table_like_mut())
+        .expect("刚刚确保过该 key 是表");
+
+    let existing_keys: Vec<String> = target.iter().map(|(k, _)| k.to_string()).collect();
+    for removed in existing_keys.iter().filter(|k| !desired_sub.contains_key(k.as_str())) {
+        target.remove(removed);
+    }
+
+    for (id, desired_item) in desired_sub.iter() {
+        let unchanged = target
+            .get(id)
+            .is_some_and(|existing| existing.to_string() == desired_item.to_string());
+        if !unchanged {
+            target.insert(id, desired_item.clone());
+        }
+    }
+}
+
+/// 将前端传来的 JSON 值转换为 `toml_edit::Value`，供 `set_config_value` 使用
+pub fn json_to_toml_value(json: serde_json::Value) -> Result<toml_edit::Value, FilterError> {
+    match json {
+        serde_json::Value::String(s) => Ok(toml_edit::Value::from(s)),
+        serde_json::Value::Bool(b) => Ok(toml_edit::Value::from(b)),
+        serde_json::Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Ok(toml_edit::Value::from(i))
+            } else if let Some(f) = n.as_f64() {
+                Ok(toml_edit::Value::from(f))
+            } else {
+                Err(FilterError::InvalidConfig(format!("不支持的数值: {}", n)))
+            }
+        }
+        other => Err(FilterError::InvalidConfig(format!(
+            "不支持写入该类型的字段值: {}",
+            other
+        ))),
+    }
+}
+
+/// 对 TOML 预设文件中某个嵌套字段做原地编辑，不重新序列化整份文档
+///
+/// `dotted_key` 是以 `.` 分隔的字段路径，例如 `presets.custom_x.config.gamma`。
+/// 仅支持 TOML；JSON 没有"保留注释"的诉求，改字段直接走 `save_presets` 即可
+pub fn set_config_value(
+    path: &Path,
+    dotted_key: &str,
+    value: toml_edit::Value,
+) -> Result<(), FilterError> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| FilterError::ConfigRead(e.to_string()))?;
+
+    let mut doc = content
+        .parse::<toml_edit::DocumentMut>()
+        .map_err(|e| FilterError::ConfigParse(e.to_string()))?;
+
+    let segments: Vec<&str> = dotted_key.split('.').collect();
+    let (last, parents) = segments
+        .split_last()
+        .ok_or_else(|| FilterError::InvalidConfig(format!("无效的字段路径: {}", dotted_key)))?;
+
+    let mut table: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for segment in parents {
+        table = table
+            .get_mut(segment)
+            .and_then(|item| item.as_table_like_mut())
+            .ok_or_else(|| {
+                FilterError::InvalidConfig(format!("字段路径不存在: {}", dotted_key))
+            })?;
+    }
+
+    table.insert(last, toml_edit::Item::Value(value));
+
+    std::fs::write(path, doc.to_string()).map_err(|e| FilterError::ConfigRead(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::types::{FilterConfig, FilterPreset, PresetCollection};
+    use std::collections::HashMap;
+
+    /// 每个测试用独立文件名，避免并行测试互相覆盖
+    fn temp_toml_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("t2_preset_store_test_{}_{:?}.toml", name, std::thread::current().id()))
+    }
+
+    fn sample_collection() -> PresetCollection {
+        let mut presets = HashMap::new();
+        presets.insert(
+            "custom_a".to_string(),
+            FilterPreset::new("custom_a".to_string(), "A".to_string(), FilterConfig::default()),
+        );
+        presets.insert(
+            "custom_b".to_string(),
+            FilterPreset::new("custom_b".to_string(), "B".to_string(), FilterConfig::default()),
+        );
+
+        PresetCollection {
+            presets,
+            active_preset_id: Some("custom_a".to_string()),
+            reset_hotkey: None,
+            profiles: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_save_presets_toml_preserves_unrelated_comments() {
+        let path = temp_toml_path("preserve_comment");
+        save_presets(&path, &sample_collection(), PresetFormat::Toml).unwrap();
+
+        // 手动在 custom_b 的子表上方插入一条注释，模拟用户手写的注释
+        let content = std::fs::read_to_string(&path).unwrap();
+        let content = content.replacen("[presets.custom_b]", "# 用户手写注释\n[presets.custom_b]", 1);
+        std::fs::write(&path, &content).unwrap();
+
+        // 只改动 custom_a 的名字，custom_b 保持不变
+        let mut presets = sample_collection();
+        presets.presets.get_mut("custom_a").unwrap().name = "A改".to_string();
+        save_presets(&path, &presets, PresetFormat::Toml).unwrap();
+
+        let result = std::fs::read_to_string(&path).unwrap();
+        assert!(result.contains("# 用户手写注释"));
+        assert!(result.contains("A改"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_save_presets_toml_drops_removed_preset() {
+        let path = temp_toml_path("drop_removed");
+        save_presets(&path, &sample_collection(), PresetFormat::Toml).unwrap();
+
+        let mut presets = sample_collection();
+        presets.presets.remove("custom_b");
+        presets.active_preset_id = Some("custom_a".to_string());
+        save_presets(&path, &presets, PresetFormat::Toml).unwrap();
+
+        let result = std::fs::read_to_string(&path).unwrap();
+        assert!(!result.contains("custom_b"));
+        assert!(result.contains("custom_a"));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,104 @@
+// 滤镜实时预览：在应用到真实硬件 Gamma Ramp 之前，抓取屏幕画面并在软件层面模拟滤镜效果
+use crate::filter::types::FilterConfig;
+use image::RgbaImage;
+
+#[cfg(target_os = "windows")]
+use windows::Win32::Graphics::Gdi::{
+    BitBlt, CreateCompatibleBitmap, CreateCompatibleDC, CreateDCW, DeleteDC, DeleteObject,
+    GetDIBits, GetDeviceCaps, SelectObject, BITMAPINFO, BITMAPINFOHEADER, BI_RGB, DIB_RGB_COLORS,
+    HORZRES, SRCCOPY, VERTRES,
+};
+#[cfg(target_os = "windows")]
+use windows::core::PCWSTR;
+
+/// 截取指定显示器的当前帧缓冲，返回 RGBA 格式图像
+///
+/// 通过 `CreateDCW` 打开显示器设备上下文，创建内存 DC 做 `BitBlt`，
+/// 再用 `GetDIBits` 读出 BGRA 像素并转换为 RGBA（交换 R/B 通道，保留 Alpha）
+#[cfg(target_os = "windows")]
+pub fn capture_monitor_rgba(monitor_device: &str) -> Result<RgbaImage, String> {
+    unsafe {
+        let device_name: Vec<u16> = monitor_device.encode_utf16().chain(std::iter::once(0)).collect();
+
+        let screen_dc = CreateDCW(PCWSTR(device_name.as_ptr()), PCWSTR::null(), PCWSTR::null(), None);
+        if screen_dc.is_invalid() {
+            return Err(format!("无法为显示器 {} 创建设备上下文", monitor_device));
+        }
+
+        let width = GetDeviceCaps(screen_dc, HORZRES);
+        let height = GetDeviceCaps(screen_dc, VERTRES);
+
+        if width <= 0 || height <= 0 {
+            let _ = DeleteDC(screen_dc);
+            return Err(format!("显示器 {} 报告的分辨率无效", monitor_device));
+        }
+
+        let mem_dc = CreateCompatibleDC(screen_dc);
+        let bitmap = CreateCompatibleBitmap(screen_dc, width, height);
+        let old_object = SelectObject(mem_dc, bitmap);
+
+        let blit_ok = BitBlt(mem_dc, 0, 0, width, height, screen_dc, 0, 0, SRCCOPY);
+
+        let mut bgra = vec![0u8; (width as usize) * (height as usize) * 4];
+        let mut bmi: BITMAPINFO = std::mem::zeroed();
+        bmi.bmiHeader.biSize = std::mem::size_of::<BITMAPINFOHEADER>() as u32;
+        bmi.bmiHeader.biWidth = width;
+        bmi.bmiHeader.biHeight = -height; // 负高度表示自上而下存储
+        bmi.bmiHeader.biPlanes = 1;
+        bmi.bmiHeader.biBitCount = 32;
+        bmi.bmiHeader.biCompression = BI_RGB.0 as u32;
+
+        let read_ok = GetDIBits(
+            mem_dc,
+            bitmap,
+            0,
+            height as u32,
+            Some(bgra.as_mut_ptr() as *mut _),
+            &mut bmi,
+            DIB_RGB_COLORS,
+        );
+
+        SelectObject(mem_dc, old_object);
+        let _ = DeleteObject(bitmap);
+        let _ = DeleteDC(mem_dc);
+        let _ = DeleteDC(screen_dc);
+
+        if !blit_ok.as_bool() || read_ok == 0 {
+            return Err(format!("无法截取显示器 {} 的画面", monitor_device));
+        }
+
+        let mut rgba = vec![0u8; bgra.len()];
+        for px in 0..(width as usize) * (height as usize) {
+            let i = px * 4;
+            rgba[i] = bgra[i + 2]; // R <- B
+            rgba[i + 1] = bgra[i + 1]; // G
+            rgba[i + 2] = bgra[i]; // B <- R
+            rgba[i + 3] = bgra[i + 3]; // A
+        }
+
+        RgbaImage::from_raw(width as u32, height as u32, rgba)
+            .ok_or_else(|| "截图像素数据大小不匹配".to_string())
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn capture_monitor_rgba(_monitor_device: &str) -> Result<RgbaImage, String> {
+    Err("屏幕截图预览仅支持 Windows 平台".to_string())
+}
+
+/// 将 `FilterConfig` 的颜色变换逐像素应用到截图上，得到"应用滤镜后会是什么样"的预览图
+///
+/// 复用 `FilterConfig::calculate_color_value` 的同一套公式，保证预览与真实 Gamma Ramp 效果一致
+pub fn apply_config_to_image(image: &RgbaImage, config: &FilterConfig) -> RgbaImage {
+    let (temp_r, temp_g, temp_b) = config.temperature_multipliers();
+    let mut out = image.clone();
+
+    for pixel in out.pixels_mut() {
+        pixel[0] = (config.calculate_color_value(config.red_scale * temp_r, pixel[0] as usize) >> 8) as u8;
+        pixel[1] = (config.calculate_color_value(config.green_scale * temp_g, pixel[1] as usize) >> 8) as u8;
+        pixel[2] = (config.calculate_color_value(config.blue_scale * temp_b, pixel[2] as usize) >> 8) as u8;
+        // Alpha 通道不受滤镜影响
+    }
+
+    out
+}
@@ -0,0 +1,179 @@
+// 按时间自动切换预设（日间/夜间等），持久化在配置目录下，与预设集合分开保存
+use serde::{Deserialize, Serialize};
+
+/// 定时表中的一条规则：从解析出的起始时刻到下一条规则的起始时刻之间，自动应用 `preset_id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    pub preset_id: String,
+
+    /// 起始时间。支持固定时刻 "HH:MM"，或相对日出/日落锚定，
+    /// 格式为 "sunrise"/"sunset"，亦可带分钟偏移，例如 "sunset-30" 表示日落前 30 分钟
+    pub start_time: String,
+
+    pub enabled: bool,
+}
+
+/// 地理位置，用于按日出/日落锚定起始时间
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GeoLocation {
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// 完整的定时表
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Schedule {
+    pub entries: Vec<ScheduleEntry>,
+    pub location: Option<GeoLocation>,
+}
+
+impl Schedule {
+    /// 解析某条规则在给定日期的起始时刻（当天的分钟数，0-1439）
+    ///
+    /// 固定时刻直接解析 "HH:MM"；日出/日落锚定需要 `location`，缺失时该条规则被忽略
+    fn resolve_start_minutes(&self, entry: &ScheduleEntry, year: i32, month: u32, day: u32) -> Option<u32> {
+        let spec = entry.start_time.trim();
+
+        if let Some(rest) = spec.strip_prefix("sunrise") {
+            let location = self.location?;
+            let offset = parse_offset_minutes(rest)?;
+            let (sunrise_minutes, _) = crate::filter::solar::sunrise_sunset_minutes(
+                location.latitude,
+                location.longitude,
+                year,
+                month,
+                day,
+            )?;
+            return Some(add_offset(sunrise_minutes, offset));
+        }
+
+        if let Some(rest) = spec.strip_prefix("sunset") {
+            let location = self.location?;
+            let offset = parse_offset_minutes(rest)?;
+            let (_, sunset_minutes) = crate::filter::solar::sunrise_sunset_minutes(
+                location.latitude,
+                location.longitude,
+                year,
+                month,
+                day,
+            )?;
+            return Some(add_offset(sunset_minutes, offset));
+        }
+
+        parse_fixed_time(spec)
+    }
+
+    /// 找出在 `now_minutes`（当天分钟数）时刻应当生效的预设 ID
+    ///
+    /// 规则按起始时刻排序后，取最后一个 "起始时刻 <= now" 的规则；如果所有规则的起始时刻
+    /// 都晚于 now，说明当前仍处于昨晚最后一条规则延续到今天凌晨的窗口内（跨越午夜），
+    /// 此时取起始时刻最晚的那条规则
+    pub fn active_preset_at(&self, now_minutes: u32, year: i32, month: u32, day: u32) -> Option<String> {
+        let mut resolved: Vec<(u32, &str)> = self
+            .entries
+            .iter()
+            .filter(|e| e.enabled)
+            .filter_map(|e| {
+                self.resolve_start_minutes(e, year, month, day)
+                    .map(|m| (m, e.preset_id.as_str()))
+            })
+            .collect();
+
+        if resolved.is_empty() {
+            return None;
+        }
+
+        resolved.sort_by_key(|(m, _)| *m);
+
+        resolved
+            .iter()
+            .rev()
+            .find(|(m, _)| *m <= now_minutes)
+            .or_else(|| resolved.last())
+            .map(|(_, preset_id)| preset_id.to_string())
+    }
+}
+
+fn parse_offset_minutes(rest: &str) -> Option<i32> {
+    if rest.is_empty() {
+        return Some(0);
+    }
+    rest.parse::<i32>().ok()
+}
+
+fn add_offset(minutes_of_day: u32, offset: i32) -> u32 {
+    let total = minutes_of_day as i32 + offset;
+    total.rem_euclid(24 * 60) as u32
+}
+
+fn parse_fixed_time(spec: &str) -> Option<u32> {
+    let (hour, minute) = spec.split_once(':')?;
+    let hour: u32 = hour.parse().ok()?;
+    let minute: u32 = minute.parse().ok()?;
+
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+
+    Some(hour * 60 + minute)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(preset_id: &str, start_time: &str) -> ScheduleEntry {
+        ScheduleEntry {
+            preset_id: preset_id.to_string(),
+            start_time: start_time.to_string(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn test_active_preset_simple() {
+        let schedule = Schedule {
+            entries: vec![entry("daytime", "07:00"), entry("nighttime", "21:00")],
+            location: None,
+        };
+
+        assert_eq!(
+            schedule.active_preset_at(8 * 60, 2026, 7, 26),
+            Some("daytime".to_string())
+        );
+        assert_eq!(
+            schedule.active_preset_at(22 * 60, 2026, 7, 26),
+            Some("nighttime".to_string())
+        );
+    }
+
+    #[test]
+    fn test_active_preset_wraps_around_midnight() {
+        let schedule = Schedule {
+            entries: vec![entry("daytime", "07:00"), entry("nighttime", "21:00")],
+            location: None,
+        };
+
+        // 凌晨 1 点，仍属于昨晚 21:00 开始的夜间窗口
+        assert_eq!(
+            schedule.active_preset_at(60, 2026, 7, 26),
+            Some("nighttime".to_string())
+        );
+    }
+
+    #[test]
+    fn test_disabled_entry_is_ignored() {
+        let mut disabled = entry("nighttime", "21:00");
+        disabled.enabled = false;
+
+        let schedule = Schedule {
+            entries: vec![entry("daytime", "07:00"), disabled],
+            location: None,
+        };
+
+        assert_eq!(
+            schedule.active_preset_at(22 * 60, 2026, 7, 26),
+            Some("daytime".to_string())
+        );
+    }
+}
@@ -0,0 +1,28 @@
+// 根据地理位置计算当地日出/日落时刻，供定时表的 sunrise/sunset 锚点使用
+use chrono::Local;
+
+/// 返回 (日出, 日落) 对应的当地时间"当天分钟数" (0-1439)
+///
+/// 使用 `sunrise` crate 基于儒略日公式计算出 UTC 时间戳，再按本机当前时区偏移换算为本地时间；
+/// 对于跨时区旅行等边缘场景这是一个近似值，但对护眼模式这类场景已经足够
+pub fn sunrise_sunset_minutes(
+    latitude: f64,
+    longitude: f64,
+    year: i32,
+    month: u32,
+    day: u32,
+) -> Option<(u32, u32)> {
+    let (sunrise_ts, sunset_ts) = sunrise::sunrise_sunset(latitude, longitude, year, month, day);
+
+    let offset_seconds = Local::now().offset().local_minus_utc() as i64;
+
+    Some((
+        unix_ts_to_minutes_of_day(sunrise_ts + offset_seconds),
+        unix_ts_to_minutes_of_day(sunset_ts + offset_seconds),
+    ))
+}
+
+fn unix_ts_to_minutes_of_day(ts: i64) -> u32 {
+    let seconds_of_day = ts.rem_euclid(24 * 3600);
+    (seconds_of_day / 60) as u32
+}
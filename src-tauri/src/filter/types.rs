@@ -1,3 +1,4 @@
+use crate::filter::FilterError;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -24,6 +25,20 @@ pub struct FilterConfig {
 
     /// 蓝色通道缩放 (0.5 - 2.0, 默认 1.0)
     pub blue_scale: f64,
+
+    /// 色温 (1000 - 10000K，6500K 为中性，默认 None 即不调整)
+    /// 数值越低越暖（偏红），数值越高越冷（偏蓝），常用于夜间护眼模式
+    pub color_temp_k: Option<u32>,
+
+    /// 是否通过 DDC/CI 硬件背光实现亮度调整，而不是通过 Gamma Ramp 重映射
+    /// 仅对支持 DDC/CI 的显示器生效，不支持时调用方应回退到 Gamma Ramp 路径
+    #[serde(default)]
+    pub use_hardware_brightness: bool,
+
+    /// `use_hardware_brightness` 为 true 时目标的硬件亮度百分比 (0-100)
+    /// 为 `None` 时即使开启了硬件亮度开关也不会下发 DDC/CI 指令
+    #[serde(default)]
+    pub hardware_brightness_percent: Option<u8>,
 }
 
 impl Default for FilterConfig {
@@ -35,26 +50,62 @@ impl Default for FilterConfig {
             red_scale: 1.0,
             green_scale: 1.0,
             blue_scale: 1.0,
+            color_temp_k: None,
+            use_hardware_brightness: false,
+            hardware_brightness_percent: None,
         }
     }
 }
 
+/// 黑体近似算法计算给定色温下的 RGB 分量 (0-255)
+///
+/// 参考 Tanner Helland 的黑体辐射近似公式，`kelvin` 取值越低越暖（偏红），越高越冷（偏蓝）
+pub(crate) fn blackbody_rgb(kelvin: u32) -> (f64, f64, f64) {
+    let t = kelvin as f64 / 100.0;
+
+    let red = if t <= 66.0 {
+        255.0
+    } else {
+        329.698727446 * (t - 60.0).powf(-0.1332047592)
+    };
+
+    let green = if t <= 66.0 {
+        99.4708025861 * t.ln() - 161.1195681661
+    } else {
+        288.1221695283 * (t - 60.0).powf(-0.0755148492)
+    };
+
+    let blue = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        138.5177312231 * (t - 10.0).ln() - 305.0447927307
+    };
+
+    (
+        red.clamp(0.0, 255.0),
+        green.clamp(0.0, 255.0),
+        blue.clamp(0.0, 255.0),
+    )
+}
+
 impl FilterConfig {
     /// 验证配置参数是否在有效范围内
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), FilterError> {
         // 验证亮度偏移
         if self.brightness < -1.0 || self.brightness > 1.0 {
-            return Err(format!("brightness 必须在 -1.0 到 1.0 之间，当前值: {}", self.brightness));
+            return Err(FilterError::InvalidConfig(format!("brightness 必须在 -1.0 到 1.0 之间，当前值: {}", self.brightness)));
         }
 
         // 验证伽马值
         if self.gamma < 0.5 || self.gamma > 3.5 {
-            return Err(format!("gamma 必须在 0.5 到 3.5 之间，当前值: {}", self.gamma));
+            return Err(FilterError::InvalidConfig(format!("gamma 必须在 0.5 到 3.5 之间，当前值: {}", self.gamma)));
         }
 
         // 验证对比度调整
         if self.contrast < -0.5 || self.contrast > 0.5 {
-            return Err(format!("contrast 必须在 -0.5 到 0.5 之间，当前值: {}", self.contrast));
+            return Err(FilterError::InvalidConfig(format!("contrast 必须在 -0.5 到 0.5 之间，当前值: {}", self.contrast)));
         }
 
         // 验证通道缩放
@@ -66,13 +117,40 @@ impl FilterConfig {
 
         for (name, value) in channels.iter() {
             if *value < 0.5 || *value > 2.0 {
-                return Err(format!("{} 必须在 0.5 到 2.0 之间，当前值: {}", name, value));
+                return Err(FilterError::InvalidConfig(format!("{} 必须在 0.5 到 2.0 之间，当前值: {}", name, value)));
+            }
+        }
+
+        // 验证色温
+        if let Some(kelvin) = self.color_temp_k {
+            if kelvin < 1000 || kelvin > 10000 {
+                return Err(FilterError::InvalidConfig(format!("color_temp_k 必须在 1000 到 10000 之间，当前值: {}", kelvin)));
+            }
+        }
+
+        // 验证硬件亮度百分比
+        if let Some(percent) = self.hardware_brightness_percent {
+            if percent > 100 {
+                return Err(FilterError::InvalidConfig(format!("hardware_brightness_percent 必须在 0 到 100 之间，当前值: {}", percent)));
             }
         }
 
         Ok(())
     }
 
+    /// 计算色温对应的 RGB 通道乘数 (0.0 - 1.0)
+    ///
+    /// `color_temp_k` 为 `None` 时返回中性值 (1.0, 1.0, 1.0)，不影响现有效果
+    pub fn temperature_multipliers(&self) -> (f64, f64, f64) {
+        match self.color_temp_k {
+            None => (1.0, 1.0, 1.0),
+            Some(kelvin) => {
+                let (r, g, b) = blackbody_rgb(kelvin);
+                (r / 255.0, g / 255.0, b / 255.0)
+            }
+        }
+    }
+
     /// 计算颜色值（应用亮度、伽马、对比度）
     ///
     /// 参数映射（符合行业标准）：
@@ -123,6 +201,11 @@ pub struct FilterPreset {
 
     /// 是否为默认预设（默认预设不可删除）
     pub is_default: bool,
+
+    /// 若该预设是从某个默认预设 `detach_preset` 而来，记录源预设 ID，
+    /// 供前端展示"派生自"信息及提供"恢复系统默认值"操作；非派生预设为 `None`
+    #[serde(default)]
+    pub derived_from: Option<String>,
 }
 
 impl FilterPreset {
@@ -134,6 +217,7 @@ impl FilterPreset {
             hotkey: None,
             config,
             is_default: false,
+            derived_from: None,
         }
     }
 
@@ -145,6 +229,7 @@ impl FilterPreset {
             hotkey,
             config,
             is_default: true,
+            derived_from: None,
         }
     }
 }
@@ -157,79 +242,117 @@ pub struct PresetCollection {
 
     /// 当前激活的预设 ID
     pub active_preset_id: Option<String>,
-}
 
-impl Default for PresetCollection {
-    fn default() -> Self {
-        let mut presets = HashMap::new();
+    /// 重置快捷键：按下后恢复到系统默认（不应用任何预设），与各预设的 `hotkey` 共享同一命名空间
+    #[serde(default)]
+    pub reset_hotkey: Option<String>,
 
-        // 默认预设 1: 标准
-        presets.insert(
+    /// 多显示器配置方案（key: profile_id, value: MonitorProfile）
+    #[serde(default)]
+    pub profiles: HashMap<String, MonitorProfile>,
+}
+
+/// 内置预设：只读常量层，不随 `PresetCollection` 一起持久化，每次按需重新构建
+///
+/// `PresetCollection.presets` 只保存用户层（自定义 + 导入的预设），`get_all_presets`/
+/// `get_preset` 等读取接口在这两层之上做合并，内置层恒定存在、不受 `reset_to_defaults`/
+/// `import_presets` 影响
+fn builtin_presets() -> HashMap<String, FilterPreset> {
+    let mut presets = HashMap::new();
+
+    // 默认预设 1: 标准
+    presets.insert(
+        "default".to_string(),
+        FilterPreset::new_default(
             "default".to_string(),
-            FilterPreset::new_default(
-                "default".to_string(),
-                "默认".to_string(),
-                Some("F2".to_string()),
-                FilterConfig::default(),
-            ),
-        );
-
-        // 默认预设 2: 白天
-        presets.insert(
+            "默认".to_string(),
+            Some("F2".to_string()),
+            FilterConfig::default(),
+        ),
+    );
+
+    // 默认预设 2: 白天
+    presets.insert(
+        "daytime".to_string(),
+        FilterPreset::new_default(
             "daytime".to_string(),
-            FilterPreset::new_default(
-                "daytime".to_string(),
-                "白天".to_string(),
-                Some("F3".to_string()),
-                FilterConfig {
-                    brightness: 0.05,   // 微增亮度
-                    gamma: 1.2,         // 轻微提亮暗部
-                    contrast: 0.05,     // 微增对比度
-                    ..FilterConfig::default()
-                },
-            ),
-        );
-
-        // 默认预设 3: 夜间
-        presets.insert(
+            "白天".to_string(),
+            Some("F3".to_string()),
+            FilterConfig {
+                brightness: 0.05,   // 微增亮度
+                gamma: 1.2,         // 轻微提亮暗部
+                contrast: 0.05,     // 微增对比度
+                ..FilterConfig::default()
+            },
+        ),
+    );
+
+    // 默认预设 3: 夜间
+    presets.insert(
+        "nighttime".to_string(),
+        FilterPreset::new_default(
             "nighttime".to_string(),
-            FilterPreset::new_default(
-                "nighttime".to_string(),
-                "夜间".to_string(),
-                Some("F4".to_string()),
-                FilterConfig {
-                    brightness: 0.3,    // 明显增加亮度
-                    gamma: 0.7,         // 提亮暗部（<1.0）
-                    contrast: 0.15,     // 增强对比度
-                    ..FilterConfig::default()
-                },
-            ),
-        );
+            "夜间".to_string(),
+            Some("F4".to_string()),
+            FilterConfig {
+                brightness: 0.3,    // 明显增加亮度
+                gamma: 0.7,         // 提亮暗部（<1.0）
+                contrast: 0.15,     // 增强对比度
+                ..FilterConfig::default()
+            },
+        ),
+    );
+
+    presets
+}
 
+impl Default for PresetCollection {
+    /// 用户层初始为空；内置预设由 `builtin_presets()` 在读取时合并进来，不写进这里
+    fn default() -> Self {
         Self {
-            presets,
+            presets: HashMap::new(),
             active_preset_id: Some("default".to_string()),
+            reset_hotkey: None,
+            profiles: HashMap::new(),
         }
     }
 }
 
 impl PresetCollection {
-    /// 添加或更新预设
+    /// 添加或更新预设（写入用户层；内置层是只读常量，不会被这里影响）
     pub fn upsert_preset(&mut self, preset: FilterPreset) {
         self.presets.insert(preset.id.clone(), preset);
     }
 
-    /// 删除预设（不能删除默认预设）
-    pub fn delete_preset(&mut self, preset_id: &str) -> Result<(), String> {
-        if let Some(preset) = self.presets.get(preset_id) {
-            if preset.is_default {
-                return Err("不能删除默认预设".to_string());
+    /// 将一批外部预设合并进用户层（用于导入分享包），ID 与现有预设（含内置层）冲突时
+    /// 重新生成 ID 并在名称上标注来源，而不是覆盖同名/同 ID 的现有预设
+    pub fn merge_presets(&mut self, incoming: Vec<FilterPreset>) -> Vec<FilterPreset> {
+        let builtins = builtin_presets();
+        let mut merged = Vec::with_capacity(incoming.len());
+
+        for mut preset in incoming {
+            if builtins.contains_key(&preset.id) || self.presets.contains_key(&preset.id) {
+                preset.id = format!("custom_{}", uuid::Uuid::new_v4().to_string());
+                preset.name = format!("{} (导入)", preset.name);
+                preset.is_default = false;
             }
+
+            self.presets.insert(preset.id.clone(), preset.clone());
+            merged.push(preset);
+        }
+
+        merged
+    }
+
+    /// 删除预设（不能删除内置预设）
+    pub fn delete_preset(&mut self, preset_id: &str) -> Result<(), FilterError> {
+        if builtin_presets().contains_key(preset_id) {
+            return Err(FilterError::InvalidConfig("不能删除默认预设".to_string()));
         }
 
         self.presets
             .remove(preset_id)
-            .ok_or_else(|| "预设不存在".to_string())?;
+            .ok_or_else(|| FilterError::PresetNotFound(preset_id.to_string()))?;
 
         // 如果删除的是当前激活的预设，切换到默认预设
         if self.active_preset_id.as_deref() == Some(preset_id) {
@@ -239,48 +362,156 @@ impl PresetCollection {
         Ok(())
     }
 
-    /// 获取预设
-    pub fn get_preset(&self, preset_id: &str) -> Option<&FilterPreset> {
-        self.presets.get(preset_id)
+    /// 获取预设（用户层优先，找不到再查内置层）
+    pub fn get_preset(&self, preset_id: &str) -> Option<FilterPreset> {
+        self.presets
+            .get(preset_id)
+            .cloned()
+            .or_else(|| builtin_presets().get(preset_id).cloned())
+    }
+
+    /// 获取所有预设（内置层 + 用户层；用户层里与内置预设同 ID 的条目是编辑内置预设
+    /// 产生的覆盖副本，会遮盖对应的内置预设，而不是与其并存）
+    pub fn get_all_presets(&self) -> Vec<FilterPreset> {
+        let mut merged = builtin_presets();
+        for (id, preset) in &self.presets {
+            merged.insert(id.clone(), preset.clone());
+        }
+        merged.into_values().collect()
     }
 
     /// 获取当前激活的预设
-    pub fn get_active_preset(&self) -> Option<&FilterPreset> {
+    pub fn get_active_preset(&self) -> Option<FilterPreset> {
         self.active_preset_id
             .as_ref()
-            .and_then(|id| self.presets.get(id))
+            .and_then(|id| self.get_preset(id))
     }
 
     /// 设置激活的预设
-    pub fn set_active_preset(&mut self, preset_id: &str) -> Result<(), String> {
-        if !self.presets.contains_key(preset_id) {
-            return Err("预设不存在".to_string());
+    pub fn set_active_preset(&mut self, preset_id: &str) -> Result<(), FilterError> {
+        if self.get_preset(preset_id).is_none() {
+            return Err(FilterError::PresetNotFound(preset_id.to_string()));
         }
         self.active_preset_id = Some(preset_id.to_string());
         Ok(())
     }
 
-    /// 验证快捷键是否冲突
-    pub fn validate_hotkey(&self, hotkey: &str, exclude_preset_id: Option<&str>) -> Result<(), String> {
-        for (id, preset) in &self.presets {
+    /// 将默认预设克隆为一份可自由编辑的用户预设
+    ///
+    /// 新预设使用新生成的 UUID、名称追加"(副本)"后缀，沿用原配置但清空继承的快捷键
+    /// （避免与 `validate_hotkey` 冲突），并记录 `derived_from` 以便前端展示派生关系、
+    /// 提供"恢复系统默认值"操作
+    pub fn detach_preset(&mut self, preset_id: &str) -> Result<String, FilterError> {
+        let source = self
+            .get_preset(preset_id)
+            .ok_or_else(|| FilterError::PresetNotFound(preset_id.to_string()))?;
+
+        let new_id = format!("custom_{}", uuid::Uuid::new_v4().to_string());
+
+        let detached = FilterPreset {
+            id: new_id.clone(),
+            name: format!("{} (副本)", source.name),
+            hotkey: None,
+            config: source.config,
+            is_default: false,
+            derived_from: Some(source.id),
+        };
+
+        self.upsert_preset(detached);
+        Ok(new_id)
+    }
+
+    /// 验证快捷键是否冲突（同时检查内置层、用户层各预设的快捷键与重置快捷键；
+    /// 用户层里覆盖了某个内置预设的条目只按合并后的那一份计算，不会把已被覆盖的
+    /// 内置快捷键也当作仍被占用）
+    pub fn validate_hotkey(&self, hotkey: &str, exclude_preset_id: Option<&str>) -> Result<(), FilterError> {
+        for preset in self.get_all_presets() {
             // 跳过要排除的预设（用于更新预设时）
-            if let Some(exclude_id) = exclude_preset_id {
-                if id == exclude_id {
-                    continue;
-                }
+            if exclude_preset_id == Some(preset.id.as_str()) {
+                continue;
             }
 
             if let Some(existing_hotkey) = &preset.hotkey {
                 if existing_hotkey == hotkey {
-                    return Err(format!(
+                    return Err(FilterError::HotkeyConflict(format!(
                         "快捷键 {} 已被预设 '{}' 使用",
                         hotkey, preset.name
-                    ));
+                    )));
+                }
+            }
+        }
+
+        if self.reset_hotkey.as_deref() == Some(hotkey) {
+            return Err(FilterError::HotkeyConflict(format!(
+                "快捷键 {} 已被重置快捷键使用",
+                hotkey
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// 设置（或清除）重置快捷键；会与内置层、用户层所有预设（合并去重后）的快捷键
+    /// 做冲突检测
+    pub fn set_reset_hotkey(&mut self, hotkey: Option<String>) -> Result<(), FilterError> {
+        if let Some(ref key) = hotkey {
+            for preset in self.get_all_presets() {
+                if preset.hotkey.as_deref() == Some(key.as_str()) {
+                    return Err(FilterError::HotkeyConflict(format!(
+                        "快捷键 {} 已被预设 '{}' 使用",
+                        key, preset.name
+                    )));
                 }
             }
         }
+
+        self.reset_hotkey = hotkey;
         Ok(())
     }
+
+    /// 添加或更新一个多显示器配置方案
+    pub fn upsert_profile(&mut self, profile: MonitorProfile) {
+        self.profiles.insert(profile.id.clone(), profile);
+    }
+
+    /// 获取配置方案
+    pub fn get_profile(&self, profile_id: &str) -> Option<&MonitorProfile> {
+        self.profiles.get(profile_id)
+    }
+
+    /// 删除配置方案
+    pub fn delete_profile(&mut self, profile_id: &str) -> Result<(), FilterError> {
+        self.profiles
+            .remove(profile_id)
+            .map(|_| ())
+            .ok_or_else(|| FilterError::PresetNotFound(profile_id.to_string()))
+    }
+}
+
+/// 某个显示器在配置方案中应使用的效果：要么直接引用一个已有预设，要么内联一份独立配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorProfileTarget {
+    /// 引用某个预设，跟随该预设后续的修改
+    Preset { preset_id: String },
+    /// 内联一份独立配置，不随任何预设变化
+    Inline { config: FilterConfig },
+}
+
+/// 多显示器配置方案：把每个显示器的设备标识映射到各自独立的效果，
+/// 使混合多屏场景（主屏冷色、副屏中性等）不必共用同一份 `FilterConfig`
+///
+/// 通过 `enumerate_monitors` 返回的稳定设备标识（`device_name`，对应 Windows 的
+/// `\\.\DISPLAY1` 这类设备名）作为 key，显示器断开重连后只要系统分配的设备名不变，
+/// 配置方案就依然生效
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorProfile {
+    /// 配置方案 ID
+    pub id: String,
+    /// 配置方案名称
+    pub name: String,
+    /// 显示器设备标识 -> 该显示器应使用的效果
+    pub assignments: HashMap<String, MonitorProfileTarget>,
 }
 
 #[cfg(test)]
@@ -310,10 +541,16 @@ mod tests {
     #[test]
     fn test_preset_collection_default() {
         let collection = PresetCollection::default();
-        assert_eq!(collection.presets.len(), 3);
-        assert!(collection.presets.contains_key("default"));
-        assert!(collection.presets.contains_key("daytime"));
-        assert!(collection.presets.contains_key("nighttime"));
+        // 默认层（用户层）为空，内置预设不持久化在 `presets` 字段里
+        assert!(collection.presets.is_empty());
+
+        // 但通过合并读取接口依然能看到 3 个内置预设
+        let all = collection.get_all_presets();
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().any(|p| p.id == "default"));
+        assert!(all.iter().any(|p| p.id == "daytime"));
+        assert!(all.iter().any(|p| p.id == "nighttime"));
+        assert!(collection.get_preset("default").is_some());
     }
 
     #[test]
@@ -323,6 +560,49 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_temperature_multipliers_neutral_when_unset() {
+        let config = FilterConfig::default();
+        assert_eq!(config.temperature_multipliers(), (1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_color_temp_validation() {
+        let mut config = FilterConfig::default();
+        config.color_temp_k = Some(6500);
+        assert!(config.validate().is_ok());
+
+        config.color_temp_k = Some(500);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_hardware_brightness_percent_validation() {
+        let mut config = FilterConfig::default();
+        config.hardware_brightness_percent = Some(100);
+        assert!(config.validate().is_ok());
+
+        config.hardware_brightness_percent = Some(101);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_detach_preset() {
+        let mut collection = PresetCollection::default();
+        let new_id = collection.detach_preset("default").unwrap();
+
+        let detached = collection.get_preset(&new_id).unwrap();
+        assert!(!detached.is_default);
+        assert_eq!(detached.derived_from.as_deref(), Some("default"));
+        assert!(detached.hotkey.is_none());
+        assert_eq!(detached.name, "默认 (副本)");
+
+        // 原始预设不受影响
+        let original = collection.get_preset("default").unwrap();
+        assert!(original.is_default);
+        assert!(original.hotkey.is_some());
+    }
+
     #[test]
     fn test_hotkey_validation() {
         let collection = PresetCollection::default();
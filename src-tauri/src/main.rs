@@ -13,7 +13,7 @@ mod filter;
 use commands::filter::FilterManagerState;
 use filter::FilterManager;
 use std::sync::Mutex;
-use tauri_plugin_global_shortcut::{GlobalShortcutExt, ShortcutState};
+use tauri_plugin_global_shortcut::ShortcutState;
 
 // 简单的测试命令 - 验证 Tauri 通信正常
 #[tauri::command]
@@ -39,11 +39,21 @@ fn main() {
             // 滤镜相关命令
             commands::filter::get_all_filter_presets,
             commands::filter::get_monitors,
+            commands::filter::preview_filter_config,
             commands::filter::get_filter_preset,
             commands::filter::create_filter_preset,
+            commands::filter::detach_filter_preset,
             commands::filter::update_filter_preset,
+            commands::filter::set_filter_config_value,
             commands::filter::delete_filter_preset,
             commands::filter::rename_filter_preset,
+            commands::filter::get_filter_reset_hotkey,
+            commands::filter::set_filter_reset_hotkey,
+            commands::filter::get_all_monitor_profiles,
+            commands::filter::create_monitor_profile,
+            commands::filter::update_monitor_profile,
+            commands::filter::delete_monitor_profile,
+            commands::filter::apply_monitor_profile,
             commands::filter::apply_filter_preset,
             commands::filter::apply_filter_config,
             commands::filter::get_active_filter_preset,
@@ -52,80 +62,33 @@ fn main() {
             commands::filter::import_filter_presets,
             commands::filter::reset_filter_presets_to_defaults,
             commands::filter::refresh_hotkey_registrations,
+            commands::filter::start_filter_hotkeys,
+            commands::filter::stop_filter_hotkeys,
+            commands::filter::get_schedule,
+            commands::filter::set_schedule,
+            commands::filter::set_schedule_preview_active,
+            commands::filter::get_ddc_capabilities,
+            commands::filter::set_hardware_brightness,
         ])
         .plugin(
             tauri_plugin_global_shortcut::Builder::new().with_handler(move |app, shortcut, event| {
-                use std::str::FromStr;
+                use filter::manager::HotkeyOutcome;
 
                 if event.state == ShortcutState::Pressed {
-                    println!("\n========== 快捷键触发 ==========");
-                    println!("触发的快捷键 Debug格式: {:?}", shortcut);
-
                     let state = app.state::<FilterManagerState>();
                     let manager_lock = state.0.lock().unwrap();
                     if let Some(manager) = manager_lock.as_ref() {
-                        // 获取所有预设，查找匹配的快捷键
-                        let presets = manager.get_all_presets();
-                        println!("当前预设数量: {}", presets.len());
-
-                        // 查找匹配快捷键的预设
-                        let matched_preset = presets.iter().find(|p| {
-                            if let Some(ref hotkey) = p.hotkey {
-                                println!("检查预设 '{}' 的快捷键: '{}'", p.name, hotkey);
-                                // 解析配置文件中的快捷键字符串
-                                match tauri_plugin_global_shortcut::Shortcut::from_str(hotkey) {
-                                    Ok(preset_shortcut) => {
-                                        // 比较两个 Shortcut 对象（使用字符串表示比较）
-                                        let a = format!("{:?}", shortcut);
-                                        let b = format!("{:?}", preset_shortcut);
-                                        println!("  触发快捷键: {}", a);
-                                        println!("  预设快捷键: {}", b);
-                                        println!("  比较结果: {}", a == b);
-                                        if a == b {
-                                            println!("✓ 快捷键匹配成功!");
-                                            return true;
-                                        }
-                                    }
-                                    Err(e) => {
-                                        println!("  解析快捷键失败: {}", e);
-                                    }
-                                }
-                            } else {
-                                println!("预设 '{}' 没有快捷键", p.name);
+                        match manager.trigger_hotkey(shortcut) {
+                            Ok(HotkeyOutcome::AppliedPreset(preset_id)) => {
+                                println!("快捷键触发，已应用预设: {}", preset_id);
+                                let _ = app.emit("preset-applied", &preset_id);
                             }
-                            false
-                        });
-
-                        if let Some(preset) = matched_preset {
-                            println!("触发快捷键，应用预设: {}", preset.name);
-
-                            // 获取所有显示器
-                            let all_monitors = match crate::filter::enumerate_monitors() {
-                                Ok(monitors) => monitors.iter().map(|m| m.device_name.clone()).collect(),
-                                Err(_) => {
-                                    eprintln!("无法枚举显示器");
-                                    vec![]
-                                }
-                            };
-
-                            if all_monitors.is_empty() {
-                                eprintln!("没有找到显示器");
-                                return;
+                            Ok(HotkeyOutcome::Reset) => {
+                                println!("快捷键触发，已重置滤镜");
+                                let _ = app.emit("filter-reset", ());
                             }
-
-                            // 应用预设
-                            match manager.apply_preset(&preset.id, all_monitors) {
-                                Ok(_) => {
-                                    println!("成功应用预设: {}", preset.name);
-                                    // 通知前端更新UI
-                                    let _ = app.emit("preset-applied", &preset.id);
-                                }
-                                Err(e) => {
-                                    eprintln!("应用预设失败: {}", e);
-                                }
-                            }
-                        } else {
-                            println!("未找到匹配的预设快捷键: {:?}", shortcut);
+                            Ok(HotkeyOutcome::NoMatch) => {}
+                            Err(e) => eprintln!("快捷键触发处理失败: {}", e),
                         }
                     }
                 }
@@ -142,22 +105,34 @@ fn main() {
 
             // 初始化滤镜管理器
             let config_dir = get_config_dir(&app_handle);
-            match FilterManager::new(config_dir) {
+            match FilterManager::new(config_dir, app_handle.clone()) {
                 Ok(manager) => {
-                    // 从管理器获取所有预设并注册快捷键
-                    let presets = manager.get_all_presets();
-                    for preset in presets {
-                        if let Some(ref hotkey) = preset.hotkey {
-                            use std::str::FromStr;
-                            if let Ok(shortcut) = tauri_plugin_global_shortcut::Shortcut::from_str(hotkey) {
-                                let _ = app_handle.global_shortcut().register(shortcut);
-                                println!("已注册快捷键: {} -> {}", hotkey, preset.name);
-                            }
-                        }
+                    // 注册所有预设及重置快捷键对应的全局快捷键
+                    if let Err(e) = manager.start_hotkeys() {
+                        eprintln!("警告：启动全局快捷键失败: {}", e);
                     }
 
                     app.manage(FilterManagerState(Mutex::new(Some(manager))));
                     println!("滤镜管理器初始化成功");
+
+                    // 启动定时调度线程：每分钟检查一次是否需要按 Schedule 自动切换预设
+                    let scheduler_app_handle = app_handle.clone();
+                    std::thread::spawn(move || loop {
+                        std::thread::sleep(std::time::Duration::from_secs(60));
+
+                        let state = scheduler_app_handle.state::<FilterManagerState>();
+                        let manager_lock = state.0.lock().unwrap();
+                        if let Some(manager) = manager_lock.as_ref() {
+                            match manager.tick_schedule() {
+                                Ok(Some(preset_id)) => {
+                                    println!("定时调度切换预设: {}", preset_id);
+                                    let _ = scheduler_app_handle.emit("preset-applied", &preset_id);
+                                }
+                                Ok(None) => {}
+                                Err(e) => eprintln!("定时调度检查失败: {}", e),
+                            }
+                        }
+                    });
                 }
                 Err(e) => {
                     eprintln!("滤镜管理器初始化失败: {}", e);